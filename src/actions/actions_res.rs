@@ -0,0 +1,340 @@
+use std::path::PathBuf;
+
+use bevy::{prelude::*, utils::HashMap};
+use serde::{Deserialize, Serialize};
+
+use crate::storage::{load_ron, project_dirs, save_ron};
+
+use super::BINDINGS_FILE;
+
+/// Logical inputs that gameplay and menu systems read instead of devices
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    MoveForward,
+    MoveBack,
+    MoveLeft,
+    MoveRight,
+    Sprint,
+    Fire,
+    Reload,
+    NextWeapon,
+    PrevWeapon,
+    Weapon1,
+    Weapon2,
+    Weapon3,
+    Weapon4,
+    /// also opens the settings from the main menu
+    Pause,
+    Confirm,
+    Back,
+    /// second menu shortcut next to Confirm
+    Alternate,
+    /// opens the co-op lobby from the main menu
+    Coop,
+    MenuUp,
+    MenuDown,
+    MenuLeft,
+    MenuRight,
+}
+
+impl Action {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Action::MoveForward => "Forward",
+            Action::MoveBack => "Back",
+            Action::MoveLeft => "Left",
+            Action::MoveRight => "Right",
+            Action::Sprint => "Sprint",
+            Action::Fire => "Shoot",
+            Action::Reload => "Reload",
+            Action::NextWeapon => "Next Weapon",
+            Action::PrevWeapon => "Prev Weapon",
+            Action::Weapon1 => "Weapon 1",
+            Action::Weapon2 => "Weapon 2",
+            Action::Weapon3 => "Weapon 3",
+            Action::Weapon4 => "Weapon 4",
+            Action::Pause => "Pause",
+            Action::Confirm => "Confirm",
+            Action::Back => "Menu Back",
+            Action::Alternate => "Alternate",
+            Action::Coop => "Co-op",
+            Action::MenuUp => "Menu Up",
+            Action::MenuDown => "Menu Down",
+            Action::MenuLeft => "Menu Left",
+            Action::MenuRight => "Menu Right",
+        }
+    }
+}
+
+/// A key, mouse or gamepad button that triggers an action
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum Binding {
+    Key(KeyCode),
+    Mouse(MouseButton),
+    Gamepad(GamepadButtonType),
+    WheelUp,
+    WheelDown,
+}
+
+impl Binding {
+    pub fn is_gamepad(&self) -> bool {
+        matches!(self, Binding::Gamepad(_))
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            Binding::Key(key) => format!("{:?}", key),
+            Binding::Mouse(MouseButton::Left) => "LMB".to_string(),
+            Binding::Mouse(MouseButton::Right) => "RMB".to_string(),
+            Binding::Mouse(MouseButton::Middle) => "MMB".to_string(),
+            Binding::Mouse(MouseButton::Other(btn)) => format!("Mouse {}", btn),
+            Binding::Gamepad(btn) => match btn {
+                GamepadButtonType::South => "A".to_string(),
+                GamepadButtonType::East => "B".to_string(),
+                GamepadButtonType::West => "X".to_string(),
+                GamepadButtonType::North => "Y".to_string(),
+                GamepadButtonType::LeftTrigger => "LB".to_string(),
+                GamepadButtonType::RightTrigger => "RB".to_string(),
+                GamepadButtonType::LeftTrigger2 => "LT".to_string(),
+                GamepadButtonType::RightTrigger2 => "RT".to_string(),
+                GamepadButtonType::LeftThumb => "LS".to_string(),
+                GamepadButtonType::RightThumb => "RS".to_string(),
+                btn => format!("{:?}", btn),
+            },
+            Binding::WheelUp => "Scroll Up".to_string(),
+            Binding::WheelDown => "Scroll Down".to_string(),
+        }
+    }
+}
+
+/// Bindings of every action, saved to the user config directory
+#[derive(Resource, Serialize, Deserialize, Clone)]
+pub struct Bindings(pub HashMap<Action, Vec<Binding>>);
+
+impl Default for Bindings {
+    fn default() -> Self {
+        use Binding::*;
+        use GamepadButtonType as Btn;
+
+        Self(HashMap::from([
+            (Action::MoveForward, vec![Key(KeyCode::W)]),
+            (Action::MoveBack, vec![Key(KeyCode::S)]),
+            (Action::MoveLeft, vec![Key(KeyCode::A)]),
+            (Action::MoveRight, vec![Key(KeyCode::D)]),
+            (
+                Action::Sprint,
+                vec![Key(KeyCode::ShiftLeft), Gamepad(Btn::LeftThumb)],
+            ),
+            (
+                Action::Fire,
+                vec![Mouse(MouseButton::Left), Gamepad(Btn::RightTrigger2)],
+            ),
+            (Action::Reload, vec![Key(KeyCode::R), Gamepad(Btn::West)]),
+            (
+                Action::NextWeapon,
+                vec![WheelDown, Gamepad(Btn::RightTrigger)],
+            ),
+            (Action::PrevWeapon, vec![WheelUp, Gamepad(Btn::LeftTrigger)]),
+            (Action::Weapon1, vec![Key(KeyCode::Key1)]),
+            (Action::Weapon2, vec![Key(KeyCode::Key2)]),
+            (Action::Weapon3, vec![Key(KeyCode::Key3)]),
+            (Action::Weapon4, vec![Key(KeyCode::Key4)]),
+            (
+                Action::Pause,
+                vec![Key(KeyCode::Escape), Gamepad(Btn::Start)],
+            ),
+            (
+                Action::Confirm,
+                vec![Key(KeyCode::Return), Gamepad(Btn::South)],
+            ),
+            (
+                Action::Back,
+                vec![Key(KeyCode::Escape), Key(KeyCode::Back), Gamepad(Btn::East)],
+            ),
+            (Action::Alternate, vec![Gamepad(Btn::North)]),
+            (Action::Coop, vec![Key(KeyCode::C), Gamepad(Btn::West)]),
+            (
+                Action::MenuUp,
+                vec![Key(KeyCode::Up), Key(KeyCode::W), Gamepad(Btn::DPadUp)],
+            ),
+            (
+                Action::MenuDown,
+                vec![Key(KeyCode::Down), Key(KeyCode::S), Gamepad(Btn::DPadDown)],
+            ),
+            (
+                Action::MenuLeft,
+                vec![Key(KeyCode::Left), Key(KeyCode::A), Gamepad(Btn::DPadLeft)],
+            ),
+            (
+                Action::MenuRight,
+                vec![
+                    Key(KeyCode::Right),
+                    Key(KeyCode::D),
+                    Gamepad(Btn::DPadRight),
+                ],
+            ),
+        ]))
+    }
+}
+
+impl Bindings {
+    /// Read the bindings file, actions missing from it keep their default bindings
+    pub fn load() -> Self {
+        let mut bindings = Self::default();
+
+        if let Some(saved) = save_path().and_then(|path| load_ron::<Self>(&path)) {
+            bindings.0.extend(saved.0);
+        }
+
+        bindings
+    }
+
+    pub fn save(&self) {
+        if let Some(path) = save_path() {
+            save_ron(&path, self);
+        }
+    }
+
+    pub fn get(&self, action: Action) -> &[Binding] {
+        self.0.get(&action).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Replace the keyboard & mouse or the gamepad binding of an action
+    pub fn rebind(&mut self, action: Action, binding: Binding) {
+        let bindings = self.0.entry(action).or_default();
+        bindings.retain(|b| b.is_gamepad() != binding.is_gamepad());
+        bindings.push(binding);
+    }
+
+    /// All bindings of an action as shown in the controls menu
+    pub fn describe(&self, action: Action) -> String {
+        let labels: Vec<String> = self.get(action).iter().map(Binding::label).collect();
+
+        if labels.is_empty() {
+            "Unbound".to_string()
+        } else {
+            labels.join(" / ")
+        }
+    }
+}
+
+/// Set of actions packed into one bit per action
+#[derive(Serialize, Deserialize, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ActionSet(u32);
+
+impl ActionSet {
+    pub fn insert(&mut self, action: Action) {
+        self.0 |= 1 << action as u32;
+    }
+
+    pub fn contains(&self, action: Action) -> bool {
+        self.0 & (1 << action as u32) != 0
+    }
+
+    pub fn union(self, other: ActionSet) -> ActionSet {
+        ActionSet(self.0 | other.0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+/// State of every action this frame, updated from the bindings before Update runs
+#[derive(Resource, Serialize, Deserialize, Default, Debug, Clone, Copy, PartialEq)]
+pub struct InputActions {
+    pub(super) pressed: ActionSet,
+    pub(super) just_pressed: ActionSet,
+    pub(super) movement: Vec2,
+}
+
+impl InputActions {
+    /// Actions that don't come from a device, like those of the headless bot
+    pub fn new(pressed: ActionSet, just_pressed: ActionSet, movement: Vec2) -> Self {
+        Self {
+            pressed,
+            just_pressed,
+            movement,
+        }
+    }
+
+    pub fn pressed(&self, action: Action) -> bool {
+        self.pressed.contains(action)
+    }
+
+    pub fn just_pressed(&self, action: Action) -> bool {
+        self.just_pressed.contains(action)
+    }
+
+    /// Movement from the move keys and the left joystick, x is right and y is forward
+    pub fn movement(&self) -> Vec2 {
+        self.movement
+    }
+
+    pub fn any_just_pressed(&self) -> bool {
+        !self.just_pressed.is_empty()
+    }
+
+    pub fn just_pressed_actions(&self) -> ActionSet {
+        self.just_pressed
+    }
+
+    /// Also count actions pressed in frames since the last gameplay tick as pressed this tick
+    pub fn with_presses(mut self, presses: ActionSet) -> Self {
+        self.pressed = self.pressed.union(presses);
+        self.just_pressed = self.just_pressed.union(presses);
+        self
+    }
+}
+
+/// Device that a player's actions are read from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InputSource {
+    /// keyboard & mouse together with the menu gamepad, used when playing solo
+    Any,
+    KeyboardMouse,
+    Gamepad(Gamepad),
+}
+
+impl InputSource {
+    pub fn label(&self) -> String {
+        match self {
+            InputSource::Any => "Any".to_string(),
+            InputSource::KeyboardMouse => "Keyboard & Mouse".to_string(),
+            InputSource::Gamepad(gp) => format!("Gamepad {}", gp.id + 1),
+        }
+    }
+}
+
+/// Actions of every device on its own, so local co-op players can be told apart
+#[derive(Resource, Default)]
+pub struct DeviceActions(pub(super) HashMap<InputSource, InputActions>);
+
+impl DeviceActions {
+    pub fn get(&self, source: InputSource) -> Option<&InputActions> {
+        self.0.get(&source)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&InputSource, &InputActions)> {
+        self.0.iter()
+    }
+}
+
+/// Actions each device pressed since the last gameplay tick
+/// A press in a frame without a tick still reaches the next tick this way
+#[derive(Resource, Default)]
+pub struct PendingPresses(pub(super) HashMap<InputSource, ActionSet>);
+
+impl PendingPresses {
+    pub fn get(&self, source: InputSource) -> ActionSet {
+        self.0.get(&source).copied().unwrap_or_default()
+    }
+
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+}
+
+fn save_path() -> Option<PathBuf> {
+    project_dirs().map(|dirs| dirs.config_dir().join(BINDINGS_FILE))
+}
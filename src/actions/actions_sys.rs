@@ -0,0 +1,124 @@
+use bevy::{
+    ecs::system::SystemParam,
+    input::{gamepad::Gamepads, mouse::MouseWheel},
+    prelude::*,
+};
+
+use crate::{gamepad::gamepad_rcs::MyGamepad, settings::settings_res::Settings};
+
+use super::actions_res::*;
+
+/// Keyboard, mouse & gamepad input of this frame
+#[derive(SystemParam)]
+pub struct InputDevices<'w> {
+    keys: Res<'w, Input<KeyCode>>,
+    mouse: Res<'w, Input<MouseButton>>,
+    btns: Res<'w, Input<GamepadButton>>,
+    axis: Res<'w, Axis<GamepadAxis>>,
+    gamepads: Res<'w, Gamepads>,
+    my_gamepad: Option<Res<'w, MyGamepad>>,
+    settings: Res<'w, Settings>,
+}
+
+impl InputDevices<'_> {
+    /// Resolve every action from the bindings of a single input source
+    fn resolve(&self, bindings: &Bindings, source: InputSource, scroll: f32) -> InputActions {
+        let keyboard_mouse = matches!(source, InputSource::Any | InputSource::KeyboardMouse);
+        let gamepad = match source {
+            InputSource::Any => self.my_gamepad.as_ref().map(|gp| gp.gamepad),
+            InputSource::KeyboardMouse => None,
+            InputSource::Gamepad(gp) => Some(gp),
+        };
+
+        // (pressed, just pressed) of a single binding
+        let state = |binding: &Binding| match *binding {
+            Binding::Key(key) if keyboard_mouse => {
+                (self.keys.pressed(key), self.keys.just_pressed(key))
+            }
+            Binding::Mouse(btn) if keyboard_mouse => {
+                (self.mouse.pressed(btn), self.mouse.just_pressed(btn))
+            }
+            Binding::Gamepad(btn) => gamepad
+                .map(|gp| GamepadButton::new(gp, btn))
+                .map_or((false, false), |btn| {
+                    (self.btns.pressed(btn), self.btns.just_pressed(btn))
+                }),
+            Binding::WheelUp if keyboard_mouse => (scroll > 0.0, scroll > 0.0),
+            Binding::WheelDown if keyboard_mouse => (scroll < 0.0, scroll < 0.0),
+            _ => (false, false),
+        };
+
+        let mut actions = InputActions::default();
+
+        for (action, action_bindings) in bindings.0.iter() {
+            for (pressed, just_pressed) in action_bindings.iter().map(state) {
+                if pressed {
+                    actions.pressed.insert(*action);
+                }
+                if just_pressed {
+                    actions.just_pressed.insert(*action);
+                }
+            }
+        }
+
+        actions.movement = self.movement(&actions, gamepad);
+        actions
+    }
+
+    /// Combine the move actions with the left joystick
+    fn movement(&self, actions: &InputActions, gamepad: Option<Gamepad>) -> Vec2 {
+        let axis_value = |action_pressed: bool| if action_pressed { 1.0 } else { 0.0 };
+        let mut movement = Vec2::new(
+            axis_value(actions.pressed(Action::MoveRight))
+                - axis_value(actions.pressed(Action::MoveLeft)),
+            axis_value(actions.pressed(Action::MoveForward))
+                - axis_value(actions.pressed(Action::MoveBack)),
+        );
+
+        if let Some(gp) = gamepad {
+            let stick = |axis_type| self.axis.get(GamepadAxis::new(gp, axis_type));
+
+            if let (Some(x), Some(y)) = (
+                stick(GamepadAxisType::LeftStickX),
+                stick(GamepadAxisType::LeftStickY),
+            ) {
+                let left_joystick = Vec2::new(x, y);
+                if left_joystick.length() > self.settings.deadzone {
+                    movement += left_joystick;
+                }
+            }
+        }
+
+        movement.clamp_length_max(1.0)
+    }
+}
+
+/// Resolve the actions of all devices together for the menus,
+/// and of every device on its own for the co-op players
+pub fn update_input_actions(
+    devices: InputDevices,
+    mut wheel_evr: EventReader<MouseWheel>,
+    bindings: Res<Bindings>,
+    mut actions: ResMut<InputActions>,
+    mut device_actions: ResMut<DeviceActions>,
+) {
+    let scroll: f32 = wheel_evr.iter().map(|ev| ev.y).sum();
+
+    *actions = devices.resolve(&bindings, InputSource::Any, scroll);
+
+    let sources = [InputSource::Any, InputSource::KeyboardMouse]
+        .into_iter()
+        .chain(devices.gamepads.iter().map(InputSource::Gamepad));
+
+    device_actions.0 = sources
+        .map(|source| (source, devices.resolve(&bindings, source, scroll)))
+        .collect();
+}
+
+/// Remember presses until the next gameplay tick samples them
+pub fn latch_presses(device_actions: Res<DeviceActions>, mut pending: ResMut<PendingPresses>) {
+    for (source, actions) in device_actions.iter() {
+        let presses = pending.0.entry(*source).or_default();
+        *presses = presses.union(actions.just_pressed_actions());
+    }
+}
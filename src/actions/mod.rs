@@ -0,0 +1,33 @@
+use bevy::{input::InputSystem, prelude::*};
+
+use crate::{AppState, PauseState};
+
+pub mod actions_res;
+mod actions_sys;
+
+use actions_res::*;
+use actions_sys::*;
+
+pub const BINDINGS_FILE: &str = "bindings.ron";
+
+pub struct ActionsPlugin;
+
+impl Plugin for ActionsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Bindings::load())
+            .init_resource::<InputActions>()
+            .init_resource::<DeviceActions>()
+            .init_resource::<PendingPresses>()
+            .add_systems(
+                PreUpdate,
+                (
+                    update_input_actions,
+                    latch_presses
+                        .run_if(in_state(AppState::Game))
+                        .run_if(in_state(PauseState::Running)),
+                )
+                    .chain()
+                    .after(InputSystem),
+            );
+    }
+}
@@ -0,0 +1,22 @@
+use bevy::prelude::*;
+
+/// Plays the player in place of a keyboard or gamepad, writing their input every tick
+#[derive(Component)]
+pub struct BotController {
+    /// side the bot circles enemies on, 1.0 is clockwise
+    pub strafe: f32,
+    /// near the edge of the map last tick
+    pub at_edge: bool,
+}
+
+impl Default for BotController {
+    fn default() -> Self {
+        Self {
+            strafe: 1.0,
+            at_edge: false,
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct DemoBanner;
@@ -0,0 +1,9 @@
+use bevy::prelude::*;
+
+/// Every player of the run is played by a BotController, for soak tests and the demo
+#[derive(Resource)]
+pub struct BotPlayers;
+
+/// Attract-mode run started from an idle main menu, any button returns to the menu
+#[derive(Resource)]
+pub struct Demo;
@@ -0,0 +1,172 @@
+use bevy::prelude::*;
+
+use crate::{
+    actions::actions_res::{Action, ActionSet, InputActions},
+    game::{
+        enemy::enemy_cmps::Enemy,
+        game_cmps::Game,
+        nav::nav_res::NavGrid,
+        player::player_cmps::{LivingPlayer, PlayerInput},
+        powerups::powerups_cmps::PowerUp,
+        replay::replay_res::GameTick,
+        world::world_res::Level,
+    },
+    AppState,
+};
+
+use super::{bot_cmps::*, *};
+
+/// Aim at the nearest enemy & shoot it, kite the enemies that get close, sprint when surrounded
+/// and pick up powerups while it's safe, through the same input a device would give
+pub fn drive_bots(
+    time: Res<FixedTime>,
+    tick: Res<GameTick>,
+    mut bot_q: Query<(&Transform, &mut PlayerInput, &mut BotController), LivingPlayer>,
+    enemy_q: Query<&Transform, With<Enemy>>,
+    powerup_q: Query<&Transform, With<PowerUp>>,
+    level: Res<Level>,
+    grid: Res<NavGrid>,
+) {
+    let enemies: Vec<Vec3> = enemy_q
+        .iter()
+        .map(|trans| flat(trans.translation))
+        .collect();
+    let powerups: Vec<Vec3> = powerup_q
+        .iter()
+        .map(|trans| flat(trans.translation))
+        .collect();
+
+    for (trans, mut input, mut bot) in bot_q.iter_mut() {
+        let pos = flat(trans.translation);
+        let target = nearest(pos, &enemies);
+
+        let mut pressed = ActionSet::default();
+        let mut just_pressed = ActionSet::default();
+
+        if let Some(target) = target {
+            let to_target = target - pos;
+
+            if to_target.length_squared() > 0.0 {
+                let aim = Transform::IDENTITY.looking_to(to_target, Vec3::Y).rotation;
+                let turn = (BOT_TURN_SPEED * time.period.as_secs_f32()).min(1.0);
+                input.aim = input.aim.slerp(aim, turn);
+
+                let on_target = input.aim().forward().angle_between(to_target) < BOT_AIM_TOLERANCE;
+
+                // release the trigger every other tick so semi-auto weapons keep firing
+                if on_target && to_target.length() < BOT_FIRE_RANGE && tick.0.is_multiple_of(2) {
+                    pressed.insert(Action::Fire);
+                    just_pressed.insert(Action::Fire);
+                }
+            }
+        }
+
+        let surrounding = enemies
+            .iter()
+            .filter(|enemy| enemy.distance(pos) < BOT_SURROUNDED_RADIUS)
+            .count();
+
+        if surrounding >= BOT_SURROUNDED_COUNT {
+            pressed.insert(Action::Sprint);
+        }
+
+        let powerup = nearest(pos, &powerups);
+        let dir = steer(&mut bot, &level, &grid, pos, &enemies, target, powerup);
+
+        // movement is relative to the aim, like it is to the camera for a device
+        let aim = input.aim();
+        let movement =
+            Vec2::new(dir.dot(aim.right()), dir.dot(aim.forward())).clamp_length_max(1.0);
+
+        input.actions = InputActions::new(pressed, just_pressed, movement);
+    }
+}
+
+/// Direction the bot wants to move in on the ground
+fn steer(
+    bot: &mut BotController,
+    level: &Level,
+    grid: &NavGrid,
+    pos: Vec3,
+    enemies: &[Vec3],
+    target: Option<Vec3>,
+    powerup: Option<Vec3>,
+) -> Vec3 {
+    // away from every enemy within kiting distance, the closer the harder
+    let flee: Vec3 = enemies
+        .iter()
+        .filter_map(|enemy| {
+            let away = pos - *enemy;
+            let dist = away.length();
+            (dist > 0.0 && dist < BOT_KITE_DISTANCE)
+                .then(|| away / dist * (1.0 - dist / BOT_KITE_DISTANCE))
+        })
+        .sum();
+
+    let threatened = target.is_some_and(|enemy| enemy.distance(pos) < BOT_POWERUP_SAFE_DISTANCE);
+
+    let mut dir = match (powerup, target) {
+        // around tables & the bar instead of getting stuck on them
+        (Some(powerup), _) if !threatened => grid.route(pos, powerup) + flee,
+        (_, Some(enemy)) => {
+            let to_enemy = (enemy - pos).normalize_or_zero();
+            let around = Vec3::new(-to_enemy.z, 0.0, to_enemy.x) * bot.strafe;
+            around + flee * 2.0
+        }
+        _ => flee,
+    };
+
+    // keep off the walls, circling the other way after running into one
+    let bounds = level.half_size() - BOT_EDGE_MARGIN;
+    let at_edge = pos.x.abs() > bounds || pos.z.abs() > bounds;
+
+    if at_edge {
+        if !bot.at_edge && dir.dot(pos) > 0.0 {
+            bot.strafe = -bot.strafe;
+        }
+        dir -= pos.normalize_or_zero();
+    }
+    bot.at_edge = at_edge;
+
+    dir
+}
+
+fn flat(pos: Vec3) -> Vec3 {
+    Vec3::new(pos.x, 0.0, pos.z)
+}
+
+fn nearest(pos: Vec3, others: &[Vec3]) -> Option<Vec3> {
+    others
+        .iter()
+        .copied()
+        .min_by(|a, b| a.distance_squared(pos).total_cmp(&b.distance_squared(pos)))
+}
+
+/// Any button ends the demo
+pub fn leave_demo(actions: Res<InputActions>, mut next_app_state: ResMut<NextState<AppState>>) {
+    if actions.any_just_pressed() {
+        next_app_state.set(AppState::MainMenu);
+    }
+}
+
+pub fn spawn_demo_banner(mut cmds: Commands, assets: Res<AssetServer>) {
+    cmds.spawn((
+        TextBundle::from_section(
+            "Demo - press any button",
+            TextStyle {
+                color: Color::WHITE,
+                font: assets.load("fonts/PermanentMarker-Regular.ttf"),
+                font_size: DEMO_BANNER_FONT_SIZE,
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            bottom: Val::Percent(5.0),
+            left: Val::Percent(40.0),
+            ..default()
+        }),
+        DemoBanner,
+        Game,
+        Name::new("Demo Banner"),
+    ));
+}
@@ -0,0 +1,47 @@
+use bevy::prelude::*;
+
+pub mod bot_cmps;
+pub mod bot_res;
+mod bot_sys;
+
+use bot_res::*;
+use bot_sys::*;
+
+use crate::{
+    game::{replay::SampleInputSet, GameplaySet},
+    AppState,
+};
+
+/// enemies closer than this are run away from
+pub const BOT_KITE_DISTANCE: f32 = 6.0;
+/// this many enemies within the radius make the bot sprint
+pub const BOT_SURROUNDED_RADIUS: f32 = 4.0;
+pub const BOT_SURROUNDED_COUNT: usize = 3;
+/// powerups are only picked up while no enemy is this close
+pub const BOT_POWERUP_SAFE_DISTANCE: f32 = 3.0;
+pub const BOT_FIRE_RANGE: f32 = 15.0;
+/// radians the aim may be off its target and still shoot
+pub const BOT_AIM_TOLERANCE: f32 = 0.3;
+/// how quickly the aim turns towards its target, higher is snappier
+pub const BOT_TURN_SPEED: f32 = 15.0;
+/// distance from the walls where the bot turns back to the middle
+pub const BOT_EDGE_MARGIN: f32 = 3.0;
+pub const DEMO_BANNER_FONT_SIZE: f32 = 30.0;
+
+pub struct BotPlugin;
+
+impl Plugin for BotPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(FixedUpdate, drive_bots.in_set(SampleInputSet))
+            .add_systems(
+                OnEnter(AppState::Game),
+                spawn_demo_banner.run_if(resource_exists::<Demo>()),
+            )
+            .add_systems(
+                Update,
+                leave_demo
+                    .run_if(resource_exists::<Demo>())
+                    .in_set(GameplaySet),
+            );
+    }
+}
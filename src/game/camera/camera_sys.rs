@@ -1,16 +1,35 @@
-use crate::game::game_cmps::Game;
-use bevy::prelude::*;
+use std::f32::consts::PI;
+
+use crate::{
+    actions::actions_res::InputSource,
+    game::{
+        game_cmps::Game,
+        player::{
+            player_cmps::{ControlledBy, Player, PlayerCam, PlayerId},
+            player_res::Party,
+        },
+    },
+    gamepad::GAMEPAD_SENSITIVITY,
+    settings::settings_res::Settings,
+};
+use bevy::{
+    core_pipeline::clear_color::ClearColorConfig,
+    input::mouse::MouseMotion,
+    prelude::*,
+    render::camera::Viewport,
+    window::{CursorGrabMode, PrimaryWindow},
+};
 use bevy_third_person_camera::{Offset, ThirdPersonCamera};
 
-pub fn spawn(mut cmds: Commands) {
+use super::split_screen_rect;
+
+/// A camera for every player, split screen cameras leave the UI to one camera over the whole window
+pub fn spawn(mut cmds: Commands, settings: Res<Settings>, party: Res<Party>) {
     let translation = Vec3::new(0.0, 1.0, 2.0);
+    let split_screen = party.0.len() > 1;
 
-    cmds.spawn((
-        Camera3dBundle {
-            transform: Transform::from_translation(translation).looking_at(Vec3::ZERO, Vec3::Y),
-            ..default()
-        },
-        ThirdPersonCamera {
+    for idx in 0..party.0.len() {
+        let mut cam = ThirdPersonCamera {
             aim_enabled: true,
             aim_zoom: 1.0,
             offset_enabled: true,
@@ -19,8 +38,214 @@ pub fn spawn(mut cmds: Commands) {
             offset: Offset::new(0.7, 0.5),
             zoom_enabled: false,
             ..default()
-        },
-        Name::new("Camera"),
-        Game,
-    ));
+        };
+        apply_sensitivity(&mut cam, &settings);
+
+        let mut camera = cmds.spawn((
+            Camera3dBundle {
+                camera: Camera {
+                    order: idx as isize,
+                    ..default()
+                },
+                transform: Transform::from_translation(translation).looking_at(Vec3::ZERO, Vec3::Y),
+                ..default()
+            },
+            cam,
+            PlayerId(idx),
+            Name::new(format!("Player {} Camera", idx + 1)),
+            Game,
+        ));
+
+        if split_screen {
+            camera.insert(UiCameraConfig { show_ui: false });
+        }
+    }
+
+    if split_screen {
+        cmds.spawn((
+            Camera2dBundle {
+                camera: Camera {
+                    order: party.0.len() as isize,
+                    ..default()
+                },
+                camera_2d: Camera2d {
+                    clear_color: ClearColorConfig::None,
+                },
+                ..default()
+            },
+            Name::new("UI Camera"),
+            Game,
+        ));
+    }
+}
+
+/// Follow sensitivity changes made in the settings menu
+pub fn apply_camera_settings(settings: Res<Settings>, mut cam_q: Query<&mut ThirdPersonCamera>) {
+    for mut cam in cam_q.iter_mut() {
+        apply_sensitivity(&mut cam, &settings);
+    }
+}
+
+/// The camera has no invert option, so flip the mouse motion it is about to read
+pub fn invert_mouse_y(mut motion_evs: ResMut<Events<MouseMotion>>) {
+    let inverted: Vec<MouseMotion> = motion_evs
+        .drain()
+        .map(|ev| MouseMotion {
+            delta: Vec2::new(ev.delta.x, -ev.delta.y),
+        })
+        .collect();
+
+    motion_evs.extend(inverted);
+}
+
+pub fn invert_y_enabled(settings: Res<Settings>) -> bool {
+    settings.invert_y
+}
+
+/// Give every player camera its part of the window, following window resizes
+pub fn split_screen(
+    window_q: Query<&Window, With<PrimaryWindow>>,
+    party: Res<Party>,
+    mut cam_q: Query<(&mut Camera, &PlayerId)>,
+) {
+    let Ok(window) = window_q.get_single() else { return };
+    let window_size = Vec2::new(
+        window.physical_width() as f32,
+        window.physical_height() as f32,
+    );
+
+    // minimized
+    if window_size.min_element() < 1.0 {
+        return;
+    }
+
+    for (mut camera, id) in cam_q.iter_mut() {
+        let rect = split_screen_rect(id.0, party.0.len());
+        let position = (rect.min * window_size).as_uvec2();
+        let size = (rect.size() * window_size).as_uvec2().max(UVec2::ONE);
+
+        let current = camera
+            .viewport
+            .as_ref()
+            .map(|viewport| (viewport.physical_position, viewport.physical_size));
+
+        if current != Some((position, size)) {
+            camera.viewport = Some(Viewport {
+                physical_position: position,
+                physical_size: size,
+                ..default()
+            });
+        }
+    }
+}
+
+/// Keep the cursor locked while the cameras are, the pause menu frees it
+pub fn lock_cursor(
+    cam_q: Query<&ThirdPersonCamera>,
+    mut window_q: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    let Ok(mut window) = window_q.get_single_mut() else { return };
+    let locked = cam_q.iter().any(|cam| cam.cursor_lock_active);
+
+    let grab_mode = if locked {
+        CursorGrabMode::Locked
+    } else {
+        CursorGrabMode::None
+    };
+
+    if window.cursor.grab_mode != grab_mode {
+        window.cursor.grab_mode = grab_mode;
+        window.cursor.visible = !locked;
+    }
+}
+
+/// Orbit each player's camera with their mouse or right joystick
+pub fn orbit_cameras(
+    window_q: Query<&Window, With<PrimaryWindow>>,
+    mut motion_evr: EventReader<MouseMotion>,
+    axis: Res<Axis<GamepadAxis>>,
+    settings: Res<Settings>,
+    player_q: Query<(&PlayerId, &ControlledBy), With<Player>>,
+    mut cam_q: Query<(&ThirdPersonCamera, &mut Transform, &PlayerId)>,
+) {
+    let Ok(window) = window_q.get_single() else { return };
+    let mouse_motion: Vec2 = motion_evr.iter().map(|ev| ev.delta).sum();
+
+    for (cam, mut cam_transform, id) in cam_q.iter_mut() {
+        if !cam.cursor_lock_active {
+            continue;
+        }
+
+        let Some((_, controlled_by)) = player_q.iter().find(|(player_id, _)| *player_id == id)
+        else {
+            continue;
+        };
+
+        let (delta_x, delta_y) = match controlled_by.0 {
+            InputSource::Gamepad(gp) => {
+                let stick = |axis_type| axis.get(GamepadAxis::new(gp, axis_type));
+                let (Some(x), Some(y)) = (
+                    stick(GamepadAxisType::RightStickX),
+                    stick(GamepadAxisType::RightStickY),
+                ) else {
+                    continue;
+                };
+
+                if x.abs() <= settings.deadzone && y.abs() <= settings.deadzone {
+                    continue;
+                }
+
+                (
+                    x / window.width() * PI * 2.0 * cam.gamepad_settings.x_sensitivity,
+                    -y / window.height() * PI * cam.gamepad_settings.y_sensitivity,
+                )
+            }
+            _ => {
+                let rotation = mouse_motion * cam.mouse_sensitivity;
+                (
+                    rotation.x / window.width() * PI,
+                    rotation.y / window.height() * PI,
+                )
+            }
+        };
+
+        // yaw around the global y axis, pitch without flipping over the top
+        cam_transform.rotation = Quat::from_rotation_y(-delta_x) * cam_transform.rotation;
+
+        let pitched = cam_transform.rotation * Quat::from_rotation_x(-delta_y);
+        if (pitched * Vec3::Y).y > 0.0 {
+            cam_transform.rotation = pitched;
+        }
+    }
+}
+
+/// Keep each camera behind its player's shoulder
+pub fn follow_players(
+    player_q: Query<(&Transform, &PlayerId), With<Player>>,
+    mut cam_q: Query<(&ThirdPersonCamera, &mut Transform, &PlayerId), PlayerCam>,
+) {
+    for (cam, mut cam_transform, id) in cam_q.iter_mut() {
+        let Some((player_trans, _)) = player_q.iter().find(|(_, player_id)| *player_id == id)
+        else {
+            continue;
+        };
+
+        // zooming is disabled, so the radius stays halfway between the zoom bounds
+        let radius = (cam.zoom.min + cam.zoom.max) / 2.0;
+        let mut offset = Vec3::new(0.0, 0.0, radius);
+        if cam.offset_enabled {
+            offset += Vec3::new(cam.offset.offset.0, cam.offset.offset.1, 0.0);
+        }
+
+        cam_transform.translation = player_trans.translation + cam_transform.rotation * offset;
+    }
+}
+
+fn apply_sensitivity(cam: &mut ThirdPersonCamera, settings: &Settings) {
+    let invert = if settings.invert_y { -1.0 } else { 1.0 };
+
+    cam.mouse_sensitivity = settings.mouse_sensitivity;
+    cam.gamepad_settings.x_sensitivity = GAMEPAD_SENSITIVITY.0 * settings.gamepad_sensitivity;
+    cam.gamepad_settings.y_sensitivity =
+        GAMEPAD_SENSITIVITY.1 * settings.gamepad_sensitivity * invert;
 }
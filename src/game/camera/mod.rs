@@ -1,4 +1,8 @@
-use crate::AppState;
+use crate::{
+    game::{game_res::GameMode, GameplaySet, RunSetupSet},
+    settings::settings_res::Settings,
+    AppState,
+};
 use bevy::prelude::*;
 use camera_sys::*;
 
@@ -8,6 +12,46 @@ pub struct CameraPlugin;
 
 impl Plugin for CameraPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(AppState::Game), spawn);
+        app.add_systems(OnEnter(AppState::Game), spawn.after(RunSetupSet))
+            .add_systems(
+                PreUpdate,
+                invert_mouse_y
+                    .run_if(in_state(AppState::Game))
+                    .run_if(invert_y_enabled),
+            )
+            .add_systems(
+                Update,
+                apply_camera_settings
+                    .run_if(in_state(AppState::Game))
+                    .run_if(resource_changed::<Settings>()),
+            )
+            // the third person camera plugin only drives a single camera
+            .add_systems(
+                Update,
+                (
+                    split_screen,
+                    lock_cursor,
+                    (orbit_cameras, follow_players).chain().in_set(GameplaySet),
+                )
+                    .run_if(in_state(AppState::Game))
+                    .run_if(resource_equals(GameMode::Coop)),
+            );
+    }
+}
+
+/// Part of the window the camera & HUD of a player cover, from 0 to 1
+/// Two players split the window left & right, three or four get a quarter each
+pub fn split_screen_rect(idx: usize, players: usize) -> Rect {
+    match players {
+        0 | 1 => Rect::new(0.0, 0.0, 1.0, 1.0),
+        2 => {
+            let x = idx as f32 * 0.5;
+            Rect::new(x, 0.0, x + 0.5, 1.0)
+        }
+        _ => {
+            let x = (idx % 2) as f32 * 0.5;
+            let y = (idx / 2) as f32 * 0.5;
+            Rect::new(x, y, x + 0.5, y + 0.5)
+        }
     }
 }
@@ -0,0 +1,128 @@
+use std::collections::HashMap;
+
+use bevy::{
+    prelude::*,
+    reflect::{TypePath, TypeUuid},
+};
+use serde::Deserialize;
+
+use crate::game::{
+    effects::effects_cmps::{EffectKind, EffectStats},
+    enemy::enemy_cmps::{EnemyKind, EnemyStats},
+    powerups::powerups_cmps::{PowerUpKind, PowerUpStats},
+    waves::waves_res::{Curve, WaveConfig},
+    weapons::weapons_cmps::{WeaponKind, WeaponStats},
+};
+
+/// Balance numbers loaded from `gameplay.config.ron`, inserted as a resource once loaded
+/// and replaced whenever the file changes on disk
+#[derive(Resource, Deserialize, TypeUuid, TypePath, Clone)]
+#[uuid = "a018c567-638e-4740-b330-77aac2323242"]
+pub struct GameConfig {
+    pub player: PlayerConfig,
+    pub enemies: HashMap<EnemyKind, EnemyStats>,
+    pub waves: WaveConfig,
+    pub weapons: HashMap<WeaponKind, WeaponStats>,
+    pub rhythm: RhythmConfig,
+    pub powerups: PowerUpConfig,
+    pub effects: HashMap<EffectKind, EffectStats>,
+}
+
+impl GameConfig {
+    pub fn enemy(&self, kind: EnemyKind) -> &EnemyStats {
+        &self.enemies[&kind]
+    }
+
+    pub fn weapon(&self, kind: WeaponKind) -> &WeaponStats {
+        &self.weapons[&kind]
+    }
+
+    pub fn effect(&self, kind: EffectKind) -> &EffectStats {
+        &self.effects[&kind]
+    }
+
+    pub fn powerup(&self, kind: PowerUpKind) -> &PowerUpStats {
+        &self.powerups.kinds[&kind]
+    }
+
+    /// Make sure every enemy, weapon, effect and powerup kind has stats
+    pub fn validate(&self) -> Result<(), String> {
+        for kind in EnemyKind::ALL {
+            if !self.enemies.contains_key(&kind) {
+                return Err(format!("missing stats for enemy {:?}", kind));
+            }
+        }
+
+        for kind in WeaponKind::STARTER {
+            if !self.weapons.contains_key(&kind) {
+                return Err(format!("missing stats for weapon {:?}", kind));
+            }
+        }
+
+        for kind in EffectKind::ALL {
+            if !self.effects.contains_key(&kind) {
+                return Err(format!("missing stats for effect {:?}", kind));
+            }
+        }
+
+        for kind in PowerUpKind::ALL {
+            if !self.powerups.kinds.contains_key(&kind) {
+                return Err(format!("missing stats for powerup {:?}", kind));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Deserialize, Clone)]
+pub struct PlayerConfig {
+    pub speed: f32,
+    /// multiplier on speed while sprinting
+    pub sprint_speed: f32,
+    pub hp: f32,
+    pub damage: f32,
+    pub stamina: f32,
+    /// seconds after sprinting before stamina starts to regenerate
+    pub stamina_regen_time: f32,
+    /// stamina regenerated per frame
+    pub stamina_regen_amount: f32,
+    /// seconds a teammate has to stay next to a downed player to revive them
+    pub revive_time: f32,
+    pub revive_radius: f32,
+    /// fraction of max hp a revived player gets back
+    pub revive_hp: f32,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct RhythmConfig {
+    /// seconds either side of a beat in which a shot counts as on-beat
+    pub on_beat_window: f32,
+    pub on_beat_dmg_bonus: f32,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct PowerUpConfig {
+    /// seconds between powerup spawns
+    pub spawn_time: f32,
+    /// seconds a powerup stays on the floor before despawning
+    pub lifetime: f32,
+    /// seconds before despawning that a powerup starts blinking
+    pub blink_time: f32,
+    #[serde(default)]
+    pub magnet: Option<PowerUpMagnet>,
+    /// scales the loot drop chances of enemies for every wave after the first
+    pub loot_curve: Curve,
+    pub kinds: HashMap<PowerUpKind, PowerUpStats>,
+}
+
+/// Pulls powerups within radius toward the closest player
+#[derive(Deserialize, Clone, Copy)]
+pub struct PowerUpMagnet {
+    pub radius: f32,
+    /// units per second
+    pub speed: f32,
+}
+
+#[derive(Resource)]
+pub struct GameConfigHandle(pub Handle<GameConfig>);
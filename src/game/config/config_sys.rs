@@ -0,0 +1,39 @@
+use bevy::prelude::*;
+
+use super::{config_res::*, GAME_CONFIG_PATH};
+
+pub fn load_game_config(mut cmds: Commands, assets: Res<AssetServer>) {
+    cmds.insert_resource(GameConfigHandle(assets.load(GAME_CONFIG_PATH)));
+}
+
+/// Copy the config asset into the GameConfig resource whenever it is (re)loaded
+/// Invalid edits are rejected so a run in progress keeps the last good config
+pub fn sync_game_config(
+    mut cmds: Commands,
+    mut config_evr: EventReader<AssetEvent<GameConfig>>,
+    configs: Res<Assets<GameConfig>>,
+    config_handle: Res<GameConfigHandle>,
+) {
+    for ev in config_evr.iter() {
+        match ev {
+            AssetEvent::Created { handle } | AssetEvent::Modified { handle } => {
+                if *handle != config_handle.0 {
+                    continue;
+                }
+
+                let Some(config) = configs.get(handle) else {
+                    continue;
+                };
+
+                match config.validate() {
+                    Ok(()) => {
+                        info!("Loaded gameplay config");
+                        cmds.insert_resource(config.clone());
+                    }
+                    Err(e) => error!("Invalid gameplay config: {}", e),
+                }
+            }
+            AssetEvent::Removed { .. } => (),
+        }
+    }
+}
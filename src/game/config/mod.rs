@@ -0,0 +1,21 @@
+use bevy::prelude::*;
+use bevy_common_assets::ron::RonAssetPlugin;
+
+pub mod config_res;
+mod config_sys;
+
+use config_res::*;
+use config_sys::*;
+
+/// the `.config.ron` extension keeps the config loader off the other `.ron` assets
+pub const GAME_CONFIG_PATH: &str = "config/gameplay.config.ron";
+
+pub struct ConfigPlugin;
+
+impl Plugin for ConfigPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(RonAssetPlugin::<GameConfig>::new(&["config.ron"]))
+            .add_systems(Startup, load_game_config)
+            .add_systems(Update, sync_game_config);
+    }
+}
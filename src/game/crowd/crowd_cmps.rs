@@ -0,0 +1,20 @@
+use bevy::prelude::*;
+
+/// Entity moved by the crowd steering instead of writing its Transform directly
+#[derive(Component, Default)]
+pub struct CrowdAgent {
+    pub radius: f32,
+    /// velocity the agent would like to move at, set by its own AI
+    pub preferred_velocity: Vec3,
+    /// velocity after avoiding the other agents
+    pub velocity: Vec3,
+}
+
+impl CrowdAgent {
+    pub fn new(radius: f32) -> Self {
+        Self {
+            radius,
+            ..default()
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use bevy::{prelude::*, utils::HashMap};
+
+use super::CROWD_CELL_SIZE;
+
+pub struct HashedAgent {
+    pub ent: Entity,
+    pub pos: Vec3,
+    pub velocity: Vec3,
+    pub radius: f32,
+}
+
+/// Crowd agents bucketed by position so neighbour lookups don't scan the whole horde
+#[derive(Resource, Default)]
+pub struct SpatialHash {
+    pub cells: HashMap<(i32, i32), Vec<HashedAgent>>,
+}
+
+impl SpatialHash {
+    fn key(pos: Vec3) -> (i32, i32) {
+        (
+            (pos.x / CROWD_CELL_SIZE).floor() as i32,
+            (pos.z / CROWD_CELL_SIZE).floor() as i32,
+        )
+    }
+
+    pub fn clear(&mut self) {
+        for agents in self.cells.values_mut() {
+            agents.clear();
+        }
+    }
+
+    pub fn insert(&mut self, agent: HashedAgent) {
+        self.cells
+            .entry(Self::key(agent.pos))
+            .or_default()
+            .push(agent);
+    }
+
+    /// Agents in the cells overlapping a circle around `pos`
+    pub fn neighbours(&self, pos: Vec3, radius: f32) -> impl Iterator<Item = &HashedAgent> {
+        let (x, z) = Self::key(pos);
+        let reach = (radius / CROWD_CELL_SIZE).ceil() as i32;
+
+        (-reach..=reach)
+            .flat_map(move |dz| (-reach..=reach).map(move |dx| (x + dx, z + dz)))
+            .filter_map(|key| self.cells.get(&key))
+            .flatten()
+    }
+}
@@ -0,0 +1,115 @@
+use bevy::prelude::*;
+
+use crate::game::game_cmps::Speed;
+
+use super::{crowd_cmps::CrowdAgent, crowd_res::*, *};
+
+pub fn update_spatial_hash(
+    mut hash: ResMut<SpatialHash>,
+    agent_q: Query<(Entity, &Transform, &CrowdAgent)>,
+) {
+    hash.clear();
+
+    for (ent, trans, agent) in agent_q.iter() {
+        hash.insert(HashedAgent {
+            ent,
+            pos: trans.translation,
+            velocity: agent.velocity,
+            radius: agent.radius,
+        });
+    }
+}
+
+/// Blend each agent's preferred velocity with separation, alignment and
+/// reciprocal collision avoidance against its neighbours, then move it
+pub fn steer_agents(
+    time: Res<FixedTime>,
+    hash: Res<SpatialHash>,
+    mut agent_q: Query<(Entity, &mut Transform, &mut CrowdAgent, &Speed)>,
+) {
+    for (ent, mut trans, mut agent, speed) in agent_q.iter_mut() {
+        let pos = Vec3::new(trans.translation.x, 0.0, trans.translation.z);
+
+        let mut separation = Vec3::ZERO;
+        let mut avoidance = Vec3::ZERO;
+        let mut alignment = Vec3::ZERO;
+        let mut neighbours = 0;
+
+        for other in hash.neighbours(pos, CROWD_NEIGHBOUR_RADIUS) {
+            if other.ent == ent {
+                continue;
+            }
+
+            let other_pos = Vec3::new(other.pos.x, 0.0, other.pos.z);
+            let offset = pos - other_pos;
+            let distance = offset.length();
+            if distance > CROWD_NEIGHBOUR_RADIUS {
+                continue;
+            }
+
+            let combined_radius = agent.radius + other.radius;
+            neighbours += 1;
+            alignment += other.velocity;
+
+            // push apart harder the more the agents overlap
+            let min_distance = combined_radius + CROWD_SEPARATION_BUFFER;
+            if distance < min_distance {
+                let away = if distance > f32::EPSILON {
+                    offset / distance
+                } else {
+                    // stacked exactly on top of each other, split them up deterministically
+                    let angle = (ent.index() as f32).sin() * std::f32::consts::TAU;
+                    Vec3::new(angle.cos(), 0.0, angle.sin())
+                };
+                separation += away * (min_distance - distance) / min_distance;
+            }
+
+            // reciprocal avoidance: steer away from where the pair will collide,
+            // each agent takes half of the responsibility
+            if let Some(ttc) =
+                time_to_collision(offset, agent.velocity - other.velocity, combined_radius)
+            {
+                if ttc < CROWD_TIME_HORIZON {
+                    let future_offset = offset + (agent.velocity - other.velocity) * ttc;
+                    let away = future_offset.normalize_or_zero();
+                    avoidance += away * 0.5 * (CROWD_TIME_HORIZON - ttc) / (ttc + 0.1);
+                }
+            }
+        }
+
+        if neighbours > 0 {
+            alignment = alignment / neighbours as f32 - agent.velocity;
+        }
+
+        let mut velocity = agent.preferred_velocity
+            + separation * CROWD_SEPARATION_WEIGHT
+            + alignment * CROWD_ALIGNMENT_WEIGHT
+            + avoidance * CROWD_AVOIDANCE_WEIGHT;
+        velocity.y = 0.0;
+        velocity = velocity.clamp_length_max(speed.0.value * CROWD_MAX_SPEED_SCALE);
+
+        agent.velocity = velocity;
+        trans.translation += velocity * time.period.as_secs_f32();
+    }
+}
+
+/// Seconds until two circles `offset` apart, closing in at `relative_velocity`, touch
+/// None if they never do
+fn time_to_collision(offset: Vec3, relative_velocity: Vec3, combined_radius: f32) -> Option<f32> {
+    // solve |offset + relative_velocity * t| = combined_radius for the first t > 0
+    let a = relative_velocity.length_squared();
+    let b = offset.dot(relative_velocity);
+    let c = offset.length_squared() - combined_radius * combined_radius;
+
+    // already overlapping, separation takes care of it
+    if c < 0.0 || a < f32::EPSILON || b >= 0.0 {
+        return None;
+    }
+
+    let discriminant = b * b - a * c;
+    if discriminant < 0.0 {
+        return None;
+    }
+
+    Some((-b - discriminant.sqrt()) / a)
+}
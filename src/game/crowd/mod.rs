@@ -0,0 +1,42 @@
+use bevy::prelude::*;
+
+pub mod crowd_cmps;
+pub mod crowd_res;
+mod crowd_sys;
+
+use crowd_res::*;
+use crowd_sys::*;
+
+use crate::game::GameplaySet;
+
+/// size of a spatial hash cell, should be at least the neighbour radius
+pub const CROWD_CELL_SIZE: f32 = 1.5;
+/// agents further apart than this ignore each other
+pub const CROWD_NEIGHBOUR_RADIUS: f32 = 1.5;
+/// extra space kept between agents on top of their radii
+pub const CROWD_SEPARATION_BUFFER: f32 = 0.1;
+pub const CROWD_SEPARATION_WEIGHT: f32 = 4.0;
+pub const CROWD_ALIGNMENT_WEIGHT: f32 = 0.3;
+pub const CROWD_AVOIDANCE_WEIGHT: f32 = 1.5;
+/// seconds ahead agents look for collisions with their neighbours
+pub const CROWD_TIME_HORIZON: f32 = 1.0;
+/// how much faster than their own speed agents may move while being pushed around
+pub const CROWD_MAX_SPEED_SCALE: f32 = 1.25;
+
+/// Systems steering crowd agents, order movement intent before this set
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CrowdSet;
+
+pub struct CrowdPlugin;
+
+impl Plugin for CrowdPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SpatialHash>().add_systems(
+            FixedUpdate,
+            (update_spatial_hash, steer_agents)
+                .chain()
+                .in_set(CrowdSet)
+                .in_set(GameplaySet),
+        );
+    }
+}
@@ -0,0 +1,115 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::game::stats::stats_cmps::{ModifierOp, Stat};
+
+/// Timed buffs & debuffs on a player or enemy
+#[derive(Component, Default)]
+pub struct StatusEffects(pub Vec<StatusEffect>);
+
+impl StatusEffects {
+    /// Add an effect, or stack it onto the running one of the same kind
+    pub fn apply(&mut self, kind: EffectKind, stats: &EffectStats) {
+        let Some(effect) = self.0.iter_mut().find(|effect| effect.kind == kind) else {
+            self.0.push(StatusEffect::new(kind, stats.duration));
+            return;
+        };
+
+        match stats.stacking {
+            Stacking::Refresh => effect.timer.reset(),
+            Stacking::Extend => {
+                let extension = Duration::from_secs_f32(stats.duration);
+                effect.extended += extension;
+                let duration = effect.timer.duration() + extension;
+                effect.timer.set_duration(duration);
+            }
+            Stacking::Stack { max } => {
+                effect.stacks = (effect.stacks + 1).min(max.max(1));
+                effect.timer.reset();
+            }
+        }
+    }
+
+    pub fn has(&self, kind: EffectKind) -> bool {
+        self.0.iter().any(|effect| effect.kind == kind)
+    }
+
+    pub fn remove(&mut self, kind: EffectKind) {
+        self.0.retain(|effect| effect.kind != kind);
+    }
+
+    /// Run the timers down, dropping the effects that ran out
+    pub fn tick(&mut self, delta: Duration) {
+        for effect in self.0.iter_mut() {
+            effect.timer.tick(delta);
+        }
+
+        self.0.retain(|effect| !effect.timer.finished());
+    }
+}
+
+pub struct StatusEffect {
+    pub kind: EffectKind,
+    /// how many times it was applied, for effects that stack
+    pub stacks: u32,
+    pub timer: Timer,
+    /// time added on top of the configured duration by extending it
+    pub extended: Duration,
+}
+
+impl StatusEffect {
+    pub fn new(kind: EffectKind, duration: f32) -> Self {
+        Self {
+            kind,
+            stacks: 1,
+            timer: Timer::from_seconds(duration, TimerMode::Once),
+            extended: Duration::ZERO,
+        }
+    }
+}
+
+/// Every effect has its stats in the gameplay config
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EffectKind {
+    DamageBoost,
+    SpeedBoost,
+    /// a player's Shield soaks up damage while it lasts
+    Shield,
+    /// players fire a fan of shots instead of one
+    Multishot,
+    Frozen,
+}
+
+impl EffectKind {
+    pub const ALL: [EffectKind; 5] = [
+        EffectKind::DamageBoost,
+        EffectKind::SpeedBoost,
+        EffectKind::Shield,
+        EffectKind::Multishot,
+        EffectKind::Frozen,
+    ];
+}
+
+#[derive(Deserialize, Clone)]
+pub struct EffectStats {
+    /// shown on the HUD while the effect is active
+    pub name: String,
+    /// seconds the effect lasts
+    pub duration: f32,
+    pub stacking: Stacking,
+    /// applied once for every stack
+    pub modifiers: Vec<(Stat, ModifierOp)>,
+}
+
+/// What applying an effect that is already running does
+#[derive(Deserialize, Clone, Copy)]
+pub enum Stacking {
+    /// restart the timer
+    Refresh,
+    /// add the duration to the time left
+    Extend,
+    /// apply the modifiers once more, up to `max` times, and restart the timer
+    Stack { max: u32 },
+}
@@ -0,0 +1,46 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::game::{
+    config::config_res::GameConfig,
+    stats::stats_cmps::{ModifierSource, Modifiers},
+};
+
+use super::effects_cmps::StatusEffects;
+
+pub fn tick_status_effects(time: Res<FixedTime>, mut effects_q: Query<&mut StatusEffects>) {
+    for mut effects in effects_q.iter_mut() {
+        effects.tick(time.period);
+    }
+}
+
+/// Pick up effect durations from a reloaded gameplay config, keeping the time effects were extended by
+pub fn apply_effect_config(config: Res<GameConfig>, mut effects_q: Query<&mut StatusEffects>) {
+    for mut effects in effects_q.iter_mut() {
+        for effect in effects.0.iter_mut() {
+            let duration = Duration::from_secs_f32(config.effect(effect.kind).duration);
+            effect.timer.set_duration(duration + effect.extended);
+        }
+    }
+}
+
+/// Replace the modifiers coming from effects with the ones of the effects still active
+pub fn sync_effect_modifiers(
+    config: Res<GameConfig>,
+    mut effects_q: Query<(&StatusEffects, &mut Modifiers)>,
+) {
+    for (effects, mut modifiers) in effects_q.iter_mut() {
+        modifiers.remove_where(|source| matches!(source, ModifierSource::Effect(_)));
+
+        for effect in effects.0.iter() {
+            let source = ModifierSource::Effect(effect.kind);
+
+            for _ in 0..effect.stacks {
+                for (stat, op) in config.effect(effect.kind).modifiers.iter() {
+                    modifiers.add(source, *stat, *op);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use bevy::prelude::*;
+
+pub mod effects_cmps;
+mod effects_sys;
+
+use effects_sys::*;
+
+use crate::game::{config::config_res::GameConfig, stats::StatsSet, GameplaySet};
+
+pub struct EffectsPlugin;
+
+impl Plugin for EffectsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            (
+                (tick_status_effects, sync_effect_modifiers)
+                    .chain()
+                    .before(StatsSet),
+                apply_effect_config.run_if(resource_exists_and_changed::<GameConfig>()),
+            )
+                .in_set(GameplaySet),
+        );
+    }
+}
@@ -1,41 +1,61 @@
 use bevy::prelude::*;
 use bevy_rapier3d::prelude::*;
+use serde::Deserialize;
 
-use crate::game::game_cmps::{Damage, Game, Hp, Speed};
+use crate::game::{
+    crowd::crowd_cmps::CrowdAgent,
+    effects::effects_cmps::StatusEffects,
+    game_cmps::{Damage, Game, Hp, Speed},
+    powerups::powerups_cmps::PowerUpKind,
+    stats::stats_cmps::{DamageTaken, FireRate, Modifiers},
+};
 
-use super::{ENEMY_ATTACK_RATE, ENEMY_SIZE, ENEMY_SPEED};
+use super::EXPLOSION_DURATION;
 
 #[derive(Bundle)]
 pub struct EnemyBundle {
     pub attack_rate: AttackRate,
     pub collider: Collider,
+    pub crowd_agent: CrowdAgent,
     pub damage: Damage,
+    pub damage_taken: DamageTaken,
     pub enemy: Enemy,
+    pub fire_rate: FireRate,
     pub friction: Friction,
     pub game: Game,
     pub hp: Hp,
+    pub kind: EnemyKind,
     pub locked_axes: LockedAxes,
+    pub modifiers: Modifiers,
     pub name: Name,
     pub rigid_body: RigidBody,
     pub speed: Speed,
+    pub status_effects: StatusEffects,
 }
 
 impl EnemyBundle {
-    pub fn new(hp: f32) -> Self {
-        let size_half = ENEMY_SIZE / 2.0;
+    /// `hp_scale` and `speed_scale` are applied on top of the stats of the enemy kind
+    pub fn new(kind: EnemyKind, stats: &EnemyStats, hp_scale: f32, speed_scale: f32) -> Self {
+        let size_half = stats.size / 2.0;
 
         Self {
-            attack_rate: AttackRate::default(),
+            attack_rate: AttackRate::new(stats.attack_rate),
             collider: Collider::cylinder(size_half, size_half),
-            damage: Damage::new(10.0),
+            crowd_agent: CrowdAgent::new(size_half),
+            damage: Damage::new(stats.damage),
+            damage_taken: DamageTaken::default(),
             enemy: Enemy,
+            fire_rate: FireRate::default(),
             friction: Friction::coefficient(0.0),
             game: Game,
-            hp: Hp::new(hp),
+            hp: Hp::new(stats.hp * hp_scale),
+            kind,
             locked_axes: LockedAxes::ROTATION_LOCKED_X | LockedAxes::ROTATION_LOCKED_Z,
-            name: Name::new("Enemy"),
+            modifiers: Modifiers::default(),
+            name: Name::new(format!("{:?} Enemy", kind)),
             rigid_body: RigidBody::Dynamic,
-            speed: Speed(ENEMY_SPEED),
+            speed: Speed::new(stats.speed * speed_scale),
+            status_effects: StatusEffects::default(),
         }
     }
 }
@@ -46,8 +66,92 @@ pub struct Enemy;
 #[derive(Component)]
 pub struct AttackRate(pub Timer);
 
-impl Default for AttackRate {
+impl AttackRate {
+    pub fn new(seconds: f32) -> Self {
+        AttackRate(Timer::from_seconds(seconds, TimerMode::Repeating))
+    }
+}
+
+/// Enemy archetype, every kind-specific behaviour is looked up through its `EnemyStats`
+#[derive(Component, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EnemyKind {
+    Walker,
+    Runner,
+    Tank,
+    Bloater,
+    Spitter,
+}
+
+impl EnemyKind {
+    pub const ALL: [EnemyKind; 5] = [
+        EnemyKind::Walker,
+        EnemyKind::Runner,
+        EnemyKind::Tank,
+        EnemyKind::Bloater,
+        EnemyKind::Spitter,
+    ];
+}
+
+#[derive(Deserialize, Clone)]
+pub struct EnemyStats {
+    pub color: Color,
+    pub shape: EnemyShape,
+    pub size: f32,
+    pub hp: f32,
+    pub speed: f32,
+    pub damage: f32,
+    /// seconds between attacks
+    pub attack_rate: f32,
+    pub attack_range: f32,
+    pub attack: AttackKind,
+    pub death: DeathKind,
+    /// powerups the enemy can drop on death with their chance, at most one drops per kill
+    #[serde(default)]
+    pub loot: Vec<(PowerUpKind, f32)>,
+}
+
+#[derive(Deserialize, Clone, Copy)]
+pub enum EnemyShape {
+    Capsule,
+    Sphere,
+}
+
+#[derive(Deserialize, Clone, Copy)]
+pub enum AttackKind {
+    /// hit the player once in range
+    Melee,
+    /// keep distance and lob projectiles at the player
+    Spit { speed: f32 },
+}
+
+#[derive(Deserialize, Clone, Copy)]
+pub enum DeathKind {
+    Despawn,
+    /// damage the player if within the blast radius
+    Explode {
+        radius: f32,
+        damage: f32,
+    },
+}
+
+/// Projectile fired by spitters
+#[derive(Component)]
+pub struct Spit {
+    pub direction: Vec3,
+    pub speed: f32,
+    pub dmg: f32,
+}
+
+/// Light flash left behind by exploding enemies
+#[derive(Component)]
+pub struct Explosion {
+    pub duration: Timer,
+}
+
+impl Default for Explosion {
     fn default() -> Self {
-        AttackRate(Timer::from_seconds(ENEMY_ATTACK_RATE, TimerMode::Repeating))
+        Explosion {
+            duration: Timer::from_seconds(EXPLOSION_DURATION, TimerMode::Once),
+        }
     }
 }
@@ -1,7 +1,31 @@
-use bevy::prelude::{Entity, Event};
+use bevy::prelude::{Entity, Event, Vec3};
+
+use super::enemy_cmps::EnemyKind;
+
+#[derive(Event)]
+pub struct HitPlayerEv {
+    /// player that was hit
+    pub ent: Entity,
+    pub dmg: f32,
+}
 
 #[derive(Event)]
-pub struct HitPlayerEv(pub f32);
+pub struct EnemyDeathEv {
+    pub ent: Entity,
+    /// player whose shot landed the kill
+    pub killer: Entity,
+    pub kind: EnemyKind,
+    /// where the enemy died, loot drops here
+    pub pos: Vec3,
+    /// killed by a nuke pickup instead of a shot, those kills can't drop another nuke
+    pub nuked: bool,
+}
 
+/// Fired when a spitter spits at a player
 #[derive(Event)]
-pub struct EnemyDeathEv(pub Entity);
+pub struct SpitEv {
+    pub origin: Vec3,
+    pub direction: Vec3,
+    pub speed: f32,
+    pub dmg: f32,
+}
@@ -1,111 +1,267 @@
-use bevy::prelude::*;
+use bevy::{
+    audio::{Volume, VolumeLevel},
+    ecs::system::SystemParam,
+    prelude::*,
+};
 use rand::Rng;
 
 use crate::game::{
-    game_cmps::{Damage, Hp, Speed},
-    player::player_cmps::Player,
+    config::config_res::GameConfig,
+    crowd::crowd_cmps::CrowdAgent,
+    game_cmps::{Damage, Game, Hp, Speed},
+    game_res::{GameRng, RngStream},
+    music::music_evs::Beat,
+    nav::nav_res::NavGrid,
+    player::{player_cmps::LivingPlayer, PLAYER_SIZE},
     projectile::projectile_evs::HitEv,
-    world::MAP_SIZE,
+    stats::stats_cmps::{DamageTaken, FireRate},
+    waves::waves_res::WaveDirector,
+    world::world_res::Level,
 };
+use crate::settings::settings_res::Settings;
 
-use super::{enemy_cmps::*, enemy_evs::*, enemy_res::*, *};
+use super::{enemy_cmps::*, enemy_evs::*, *};
 
-pub fn spawn_enemy(
-    mut cmds: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
-    mut spawn_timer: ResMut<EnemySpawnTimer>,
-    time: Res<Time>,
-    enemy_hp: Res<EnemyHp>,
-) {
-    spawn_timer.0.tick(time.delta());
+/// Players enemies go after, disjoint from enemy queries on Transform
+type TargetPlayer = (LivingPlayer, Without<Enemy>);
 
-    let mut rng = rand::thread_rng();
+type DamagedEnemy<'a> = (
+    Entity,
+    &'a mut Hp,
+    &'a DamageTaken,
+    &'a Transform,
+    &'a EnemyKind,
+);
 
-    let map_bounds = MAP_SIZE / 2.0;
-    let x = rng.gen_range(-map_bounds..=map_bounds);
-    let z = rng.gen_range(-map_bounds..=map_bounds);
+type AttackingEnemy<'a> = (
+    &'a Transform,
+    &'a mut AttackRate,
+    &'a Damage,
+    &'a FireRate,
+    &'a EnemyKind,
+);
 
-    if spawn_timer.0.finished() {
-        let size_half = ENEMY_SIZE / 2.0;
-        cmds.spawn((
+/// Everything needed to put an enemy in the arena
+#[derive(SystemParam)]
+pub struct EnemySpawner<'w, 's> {
+    cmds: Commands<'w, 's>,
+    meshes: ResMut<'w, Assets<Mesh>>,
+    materials: ResMut<'w, Assets<StandardMaterial>>,
+    config: Res<'w, GameConfig>,
+}
+
+impl EnemySpawner<'_, '_> {
+    /// Spawn an enemy of kind at pos, its hp & speed scaled for the wave
+    pub fn spawn(&mut self, kind: EnemyKind, pos: Vec3, hp_scale: f32, speed_scale: f32) {
+        let stats = self.config.enemy(kind);
+
+        let size_half = stats.size / 2.0;
+        let mesh = match stats.shape {
+            EnemyShape::Capsule => Mesh::from(shape::Capsule {
+                radius: size_half,
+                depth: size_half,
+                ..default()
+            }),
+            EnemyShape::Sphere => Mesh::from(shape::UVSphere {
+                radius: size_half,
+                ..default()
+            }),
+        };
+
+        self.cmds.spawn((
             PbrBundle {
-                material: materials.add(Color::RED.into()),
-                mesh: meshes.add(Mesh::from(shape::Capsule {
-                    radius: size_half,
-                    depth: size_half,
-                    ..default()
-                })),
-                transform: Transform::from_xyz(x, 0.5, z),
+                material: self.materials.add(stats.color.into()),
+                mesh: self.meshes.add(mesh),
+                transform: Transform::from_xyz(pos.x, stats.size, pos.z),
                 ..default()
             },
-            EnemyBundle::new(enemy_hp.0),
+            EnemyBundle::new(kind, stats, hp_scale, speed_scale),
         ));
     }
 }
 
-/// Increase HP over time to raise difficulty
-pub fn increase_hp_over_time(
-    mut timer: ResMut<RaiseDifficultyTimer>,
-    mut enemy_hp: ResMut<EnemyHp>,
-    time: Res<Time>,
+/// Spawn the enemies of the current wave on the beat
+pub fn spawn_enemy(
+    mut spawner: EnemySpawner,
+    mut beat_evr: EventReader<Beat>,
+    mut director: ResMut<WaveDirector>,
+    mut game_rng: ResMut<GameRng>,
+    level: Res<Level>,
 ) {
-    if timer.0.just_finished() {
-        enemy_hp.0 += HP_GAIN;
-    }
+    let rng = game_rng.stream(RngStream::Enemies);
+
+    for ev in beat_evr.iter() {
+        if !director.should_spawn(ev.0) {
+            continue;
+        }
+
+        director.remaining -= 1;
+
+        let pos = Level::random_point(&level.enemy_spawns, rng);
+        let kind = director.spec.pick_enemy(rng);
 
-    timer.0.tick(time.delta());
+        spawner.spawn(kind, pos, director.spec.hp, director.spec.speed);
+    }
 }
 
-/// Track towards player
+/// Track towards the nearest living player along the nav grid flow field,
+/// the crowd steering does the moving
+/// Ranged enemies hold their position once the player is in range
 pub fn tracking(
-    mut enemy_q: Query<(&mut Transform, &Speed), With<Enemy>>,
-    player: Query<&Transform, (With<Player>, Without<Enemy>)>,
-    time: Res<Time>,
+    mut enemy_q: Query<(&Transform, &Speed, &EnemyKind, &mut CrowdAgent), With<Enemy>>,
+    player_q: Query<(Entity, &Transform), TargetPlayer>,
+    nav_grid: Res<NavGrid>,
+    config: Res<GameConfig>,
 ) {
-    for (mut enemy_trans, enemy_speed) in enemy_q.iter_mut() {
-        if let Ok(player_trans) = player.get_single() {
-            let direction = (player_trans.translation - enemy_trans.translation).normalize();
+    for (enemy_trans, enemy_speed, kind, mut agent) in enemy_q.iter_mut() {
+        let Some((_, player_pos)) = nearest_player(enemy_trans.translation, &player_q) else {
+            agent.preferred_velocity = Vec3::ZERO;
+            continue;
+        };
+
+        let to_player = player_pos - enemy_trans.translation;
+
+        let stats = config.enemy(*kind);
+        let hold_distance = match stats.attack {
+            AttackKind::Melee => 0.0,
+            AttackKind::Spit { .. } => stats.attack_range * SPIT_HOLD_DISTANCE,
+        };
 
-            enemy_trans.translation += direction * enemy_speed.0 * time.delta_seconds();
+        if to_player.length() <= hold_distance {
+            agent.preferred_velocity = Vec3::ZERO;
+            continue;
         }
+
+        let direction = nav_grid.direction(enemy_trans.translation, player_pos);
+
+        agent.preferred_velocity = direction * enemy_speed.0.value;
     }
 }
 
-/// Fire Hit Player Event when enemy collides with player
+/// Attack the nearest living player once in range of the enemy kind
+/// Melee enemies fire a Hit Player Event, spitters fire a Spit Event
 pub fn attack(
-    mut cmds: Commands,
-    time: Res<Time>,
-    assets: Res<AssetServer>,
+    time: Res<FixedTime>,
+    config: Res<GameConfig>,
     mut hit_player_ev: EventWriter<HitPlayerEv>,
-    mut enemy_q: Query<(&mut Transform, &mut AttackRate, &Damage), With<Enemy>>,
-    mut player: Query<&Transform, (With<Player>, Without<Enemy>)>,
+    mut spit_ev: EventWriter<SpitEv>,
+    mut enemy_q: Query<AttackingEnemy, With<Enemy>>,
+    player_q: Query<(Entity, &Transform), TargetPlayer>,
 ) {
-    for (enemy_trans, mut attack_rate, enemy_dmg) in enemy_q.iter_mut() {
-        if let Ok(player_trans) = player.get_single_mut() {
-            let distance = Vec3::distance(enemy_trans.translation, player_trans.translation);
+    for (enemy_trans, mut attack_rate, enemy_dmg, fire_rate, kind) in enemy_q.iter_mut() {
+        // a timer ticked by zero never leaves its start, which the ready check below reads as
+        // ready every tick, so stopped enemies skip attacking instead
+        if fire_rate.is_stopped() {
+            continue;
+        }
+
+        let delta = time.period.mul_f32(fire_rate.0.value);
 
-            let buffer = 0.1;
-            if distance < ENEMY_SIZE + buffer {
-                if attack_rate.0.percent_left() == 1.0 {
+        let Some((player_ent, player_pos)) = nearest_player(enemy_trans.translation, &player_q)
+        else {
+            continue;
+        };
+
+        let distance = Vec3::distance(enemy_trans.translation, player_pos);
+
+        let stats = config.enemy(*kind);
+        if distance < stats.attack_range && attack_rate.0.percent_left() == 1.0 {
+            match stats.attack {
+                AttackKind::Melee => {
                     // fire hit player event
-                    hit_player_ev.send(HitPlayerEv(enemy_dmg.value));
-                    attack_rate.0.tick(time.delta());
+                    hit_player_ev.send(HitPlayerEv {
+                        ent: player_ent,
+                        dmg: enemy_dmg.0.value,
+                    });
+                }
+                AttackKind::Spit { speed } => {
+                    let direction = (player_pos - enemy_trans.translation).normalize();
 
-                    cmds.spawn(AudioBundle {
-                        source: assets.load(r"audio\hurt.ogg"),
-                        ..default()
+                    // fire spit event
+                    spit_ev.send(SpitEv {
+                        origin: enemy_trans.translation,
+                        direction,
+                        speed,
+                        dmg: enemy_dmg.0.value,
                     });
                 }
             }
 
-            if attack_rate.0.percent_left() < 1.0 {
-                attack_rate.0.tick(time.delta());
-            }
+            attack_rate.0.tick(delta);
+        }
 
-            if attack_rate.0.finished() {
-                attack_rate.0.reset();
-            }
+        if attack_rate.0.percent_left() < 1.0 {
+            attack_rate.0.tick(delta);
+        }
+
+        if attack_rate.0.finished() {
+            attack_rate.0.reset();
+        }
+    }
+}
+
+pub fn spawn_spit(
+    mut cmds: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut spit_evr: EventReader<SpitEv>,
+) {
+    for ev in spit_evr.iter() {
+        cmds.spawn((
+            PbrBundle {
+                material: materials.add(StandardMaterial {
+                    emissive: Color::LIME_GREEN,
+                    ..default()
+                }),
+                mesh: meshes.add(Mesh::from(shape::UVSphere {
+                    radius: SPIT_SIZE,
+                    ..default()
+                })),
+                transform: Transform::from_translation(ev.origin),
+                ..default()
+            },
+            Spit {
+                direction: ev.direction,
+                speed: ev.speed,
+                dmg: ev.dmg,
+            },
+            Game,
+            Name::new("Spit"),
+        ));
+    }
+}
+
+/// Move spit towards its target, hurting the first living player it touches
+pub fn move_spit(
+    mut cmds: Commands,
+    time: Res<FixedTime>,
+    mut hit_player_ev: EventWriter<HitPlayerEv>,
+    mut spit_q: Query<(Entity, &mut Transform, &Spit)>,
+    player_q: Query<(Entity, &Transform), (TargetPlayer, Without<Spit>)>,
+    level: Res<Level>,
+) {
+    for (ent, mut trans, spit) in spit_q.iter_mut() {
+        trans.translation += spit.direction * spit.speed * time.period.as_secs_f32();
+
+        let hit = player_q
+            .iter()
+            .find(|(_, player_trans)| {
+                trans.translation.distance(player_trans.translation) < PLAYER_SIZE
+            })
+            .map(|(player_ent, _)| player_ent);
+
+        if let Some(player_ent) = hit {
+            hit_player_ev.send(HitPlayerEv {
+                ent: player_ent,
+                dmg: spit.dmg,
+            });
+            cmds.entity(ent).despawn_recursive();
+            continue;
+        }
+
+        // despawn once beyond the map bounds
+        if !level.contains(trans.translation) {
+            cmds.entity(ent).despawn_recursive();
         }
     }
 }
@@ -114,16 +270,27 @@ pub fn attack(
 pub fn decrease_hp(
     mut hit_evr: EventReader<HitEv>,
     mut death_evw: EventWriter<EnemyDeathEv>,
-    mut enemy_q: Query<(Entity, &mut Hp), With<Enemy>>,
+    mut enemy_q: Query<DamagedEnemy, With<Enemy>>,
 ) {
     for ev in hit_evr.iter() {
-        for (ent, mut hp) in enemy_q.iter_mut() {
+        for (ent, mut hp, damage_taken, trans, kind) in enemy_q.iter_mut() {
             if ent.index() == ev.ent.index() {
-                hp.value -= ev.dmg;
+                // already dead, waiting to be despawned
+                if hp.value <= 0.0 {
+                    continue;
+                }
+
+                hp.value -= ev.dmg * damage_taken.0.value;
 
                 if hp.value <= 0.0 {
                     // fire enemy death event
-                    death_evw.send(EnemyDeathEv(ent));
+                    death_evw.send(EnemyDeathEv {
+                        ent,
+                        killer: ev.owner,
+                        kind: *kind,
+                        pos: trans.translation,
+                        nuked: false,
+                    });
                 }
             }
         }
@@ -134,26 +301,163 @@ pub fn decrease_hp(
 pub fn play_hit_noise(
     mut cmds: Commands,
     assets: Res<AssetServer>,
+    settings: Res<Settings>,
     mut hit_evr: EventReader<HitEv>,
+    mut game_rng: ResMut<GameRng>,
 ) {
-    for _ev in hit_evr.iter() {
-        let num = rand::thread_rng().gen_range(0..=4);
+    for ev in hit_evr.iter() {
+        let num = game_rng.stream(RngStream::Effects).gen_range(0..=4);
         let file = format!(r"audio\enemy\hurt_{}.ogg", num);
+
+        // on-beat hits get a higher pitched hit noise
+        let speed = if ev.on_beat { ON_BEAT_HIT_PITCH } else { 1.0 };
+
         cmds.spawn(AudioBundle {
             source: assets.load(file),
-            ..default()
+            settings: PlaybackSettings {
+                speed,
+                volume: Volume::Relative(VolumeLevel::new(settings.sfx_volume())),
+                ..default()
+            },
         });
     }
 }
 
-/// Despawn enemy
-pub fn despawn(mut cmds: Commands, mut death_evr: EventReader<EnemyDeathEv>) {
+/// Run the death behaviour of the enemy kind and despawn the enemy
+pub fn despawn(
+    mut cmds: Commands,
+    mut death_evr: EventReader<EnemyDeathEv>,
+    mut hit_player_ev: EventWriter<HitPlayerEv>,
+    enemy_q: Query<(&Transform, &EnemyKind), With<Enemy>>,
+    player_q: Query<(Entity, &Transform), TargetPlayer>,
+    config: Res<GameConfig>,
+) {
     for ev in death_evr.iter() {
-        cmds.entity(ev.0).despawn_recursive();
+        if let Ok((enemy_trans, kind)) = enemy_q.get(ev.ent) {
+            match config.enemy(*kind).death {
+                DeathKind::Despawn => (),
+                DeathKind::Explode { radius, damage } => {
+                    for (player_ent, player_trans) in player_q.iter() {
+                        if enemy_trans.translation.distance(player_trans.translation) < radius {
+                            hit_player_ev.send(HitPlayerEv {
+                                ent: player_ent,
+                                dmg: damage,
+                            });
+                        }
+                    }
+
+                    cmds.spawn((
+                        PointLightBundle {
+                            point_light: PointLight {
+                                color: Color::YELLOW_GREEN,
+                                intensity: EXPLOSION_INTENSITY,
+                                range: radius * 2.0,
+                                ..default()
+                            },
+                            transform: Transform::from_translation(enemy_trans.translation),
+                            ..default()
+                        },
+                        Explosion::default(),
+                        Game,
+                        Name::new("Explosion"),
+                    ));
+                }
+            }
+        }
+
+        cmds.entity(ev.ent).despawn_recursive();
     }
 }
 
-/// Reset enemy HP (for restarting game)
-pub fn reset_hp(mut enemy_hp: ResMut<EnemyHp>) {
-    enemy_hp.0 = ENEMY_HP;
+/// Fade out explosion flashes
+pub fn fade_explosion(
+    mut cmds: Commands,
+    time: Res<Time>,
+    mut explosion_q: Query<(Entity, &mut PointLight, &mut Explosion)>,
+) {
+    for (ent, mut light, mut explosion) in explosion_q.iter_mut() {
+        explosion.duration.tick(time.delta());
+        light.intensity = EXPLOSION_INTENSITY * explosion.duration.percent_left();
+
+        if explosion.duration.finished() {
+            cmds.entity(ent).despawn_recursive();
+        }
+    }
+}
+
+/// Play hurt noise whenever a player takes damage
+pub fn play_hurt_noise(
+    mut cmds: Commands,
+    assets: Res<AssetServer>,
+    settings: Res<Settings>,
+    mut hit_player_evr: EventReader<HitPlayerEv>,
+) {
+    for _ev in hit_player_evr.iter() {
+        cmds.spawn(AudioBundle {
+            source: assets.load(r"audio\hurt.ogg"),
+            settings: PlaybackSettings {
+                volume: Volume::Relative(VolumeLevel::new(settings.sfx_volume())),
+                ..default()
+            },
+        });
+    }
+}
+
+/// Entity & position of the living player closest to a position
+fn nearest_player(
+    pos: Vec3,
+    player_q: &Query<(Entity, &Transform), TargetPlayer>,
+) -> Option<(Entity, Vec3)> {
+    player_q
+        .iter()
+        .map(|(ent, trans)| (ent, trans.translation))
+        .min_by(|(_, a), (_, b)| a.distance_squared(pos).total_cmp(&b.distance_squared(pos)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{player::player_cmps::Player, stats::stats_cmps::Attribute};
+
+    const TICKS: usize = 120;
+
+    /// Number of melee hits a walker next to a player lands over TICKS ticks at the given fire rate
+    fn hits_at(fire_rate: f32) -> usize {
+        let config: GameConfig =
+            ron::from_str(include_str!("../../../assets/config/gameplay.config.ron")).unwrap();
+        let stats = config.enemy(EnemyKind::Walker).clone();
+
+        let mut app = App::new();
+        app.insert_resource(FixedTime::new_from_secs(1.0 / 60.0))
+            .insert_resource(config)
+            // no event updates, so every hit of the run stays readable
+            .init_resource::<Events<HitPlayerEv>>()
+            .init_resource::<Events<SpitEv>>()
+            .add_systems(Update, attack);
+
+        app.world.spawn((Player, Transform::default()));
+        app.world.spawn((
+            EnemyBundle::new(EnemyKind::Walker, &stats, 1.0, 1.0),
+            Transform::from_xyz(stats.attack_range / 2.0, 0.0, 0.0),
+        ));
+
+        let mut fire_rate_q = app.world.query::<&mut FireRate>();
+        fire_rate_q.single_mut(&mut app.world).0 = Attribute {
+            base: 1.0,
+            value: fire_rate,
+        };
+
+        for _ in 0..TICKS {
+            app.update();
+        }
+
+        app.world.resource::<Events<HitPlayerEv>>().len()
+    }
+
+    /// Freeze puts the fire rate to 0, which stops attacks instead of landing one every tick
+    #[test]
+    fn frozen_enemy_does_not_attack() {
+        assert!(hits_at(1.0) > 0);
+        assert_eq!(hits_at(0.0), 0);
+    }
 }
@@ -2,44 +2,52 @@ use bevy::prelude::*;
 
 pub mod enemy_cmps;
 pub mod enemy_evs;
-pub mod enemy_res;
 mod enemy_sys;
 
-use crate::{debug::debug_res::EnableDebugMode, AppState};
+use crate::{
+    debug::debug_res::EnableDebugMode,
+    game::{crowd::CrowdSet, player::PlayerMoveSet, stats::StatsSet, AddTickEvent, GameplaySet},
+};
 use enemy_evs::*;
-use enemy_res::*;
 use enemy_sys::*;
 
-pub const ENEMY_SPAWN_TIME: f32 = 2.0;
-pub const ENEMY_SPEED: f32 = 2.6; // slightly faster than player
-pub const ENEMY_HP: f32 = 100.0;
-pub const ENEMY_SIZE: f32 = 0.5;
-pub const ENEMY_ATTACK_RATE: f32 = 2.0;
-pub const RAISE_DIFFICULTY_TIME: f32 = 20.0;
-pub const HP_GAIN: f32 = 25.0;
+/// spitters stop approaching once the player is within this fraction of their attack range
+pub const SPIT_HOLD_DISTANCE: f32 = 0.75;
+pub const SPIT_SIZE: f32 = 0.06;
+pub const EXPLOSION_DURATION: f32 = 0.4;
+pub const EXPLOSION_INTENSITY: f32 = 20000.0;
+pub const ON_BEAT_HIT_PITCH: f32 = 1.25;
+
+/// Spawning of wave enemies, the spawns go through commands
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EnemySpawnSet;
 
 pub struct EnemyPlugin;
 
 impl Plugin for EnemyPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<EnemySpawnTimer>()
-            .init_resource::<RaiseDifficultyTimer>()
-            .init_resource::<EnemyHp>()
-            .add_event::<HitPlayerEv>()
-            .add_event::<EnemyDeathEv>()
-            .add_systems(OnEnter(AppState::Game), reset_hp)
+        app.add_tick_event::<HitPlayerEv>()
+            .add_tick_event::<EnemyDeathEv>()
+            .add_tick_event::<SpitEv>()
             .add_systems(
-                Update,
+                FixedUpdate,
                 (
-                    decrease_hp,
+                    decrease_hp.after(StatsSet),
                     despawn,
-                    spawn_enemy.run_if(resource_equals(EnableDebugMode(false))),
-                    tracking,
-                    attack,
-                    increase_hp_over_time,
-                    play_hit_noise,
+                    spawn_enemy
+                        .run_if(resource_equals(EnableDebugMode(false)))
+                        .in_set(EnemySpawnSet),
+                    tracking.before(CrowdSet),
+                    // melee reach is short, hit before the players step away this tick
+                    attack.after(StatsSet).before(PlayerMoveSet),
+                    spawn_spit,
+                    move_spit,
                 )
-                    .run_if(in_state(AppState::Game)),
+                    .in_set(GameplaySet),
+            )
+            .add_systems(
+                Update,
+                (fade_explosion, play_hit_noise, play_hurt_noise).in_set(GameplaySet),
             );
     }
 }
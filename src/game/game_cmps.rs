@@ -1,29 +1,37 @@
 use bevy::prelude::*;
 
+use super::stats::stats_cmps::Attribute;
+
 #[derive(Component)]
 pub struct Hp {
-    pub max: f32,
+    pub max: Attribute,
     pub value: f32,
 }
 
 impl Hp {
     pub fn new(max: f32) -> Self {
-        Self { max, value: max }
+        Self {
+            max: Attribute::new(max),
+            value: max,
+        }
     }
 }
 
 #[derive(Component)]
-pub struct Speed(pub f32);
+pub struct Speed(pub Attribute);
 
-#[derive(Component)]
-pub struct Damage {
-    pub max: f32,
-    pub value: f32,
+impl Speed {
+    pub fn new(speed: f32) -> Self {
+        Self(Attribute::new(speed))
+    }
 }
 
+#[derive(Component)]
+pub struct Damage(pub Attribute);
+
 impl Damage {
-    pub fn new(max: f32) -> Self {
-        Self { max, value: max }
+    pub fn new(damage: f32) -> Self {
+        Self(Attribute::new(damage))
     }
 }
 
@@ -1,4 +1,98 @@
 use bevy::{prelude::*, time::Stopwatch};
+use rand::{rngs::StdRng, SeedableRng};
+use serde::{Deserialize, Serialize};
 
 #[derive(Resource, Default)]
 pub struct GameTime(pub Stopwatch);
+
+/// Simulation only: nothing is drawn, played or read from devices, set up by `--headless`
+#[derive(Resource)]
+pub struct Headless;
+
+/// How the current run is being played, recorded with high scores
+#[derive(Resource, Serialize, Deserialize, Default, Debug, Clone, Copy, PartialEq)]
+pub enum GameMode {
+    #[default]
+    Solo,
+    /// local split screen with 2 or more players
+    Coop,
+}
+
+/// What a stream of gameplay randomness is used for
+/// Every stream is seeded separately so systems drawing from one can't shift another's sequence
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RngStream {
+    Enemies,
+    PowerUps,
+    Weapons,
+    Effects,
+    Loot,
+}
+
+impl RngStream {
+    /// Every stream in declaration order, new variants go here too so they get their own seed
+    pub const ALL: [RngStream; 5] = [
+        RngStream::Enemies,
+        RngStream::PowerUps,
+        RngStream::Weapons,
+        RngStream::Effects,
+        RngStream::Loot,
+    ];
+}
+
+/// Source of all gameplay randomness, reseeded at the start of every run
+#[derive(Resource)]
+pub struct GameRng {
+    /// seed given with `--seed`, every run reuses it
+    pub fixed_seed: Option<u64>,
+    /// seed of the current run
+    pub seed: u64,
+    streams: [StdRng; RngStream::ALL.len()],
+}
+
+impl GameRng {
+    pub fn new(fixed_seed: Option<u64>) -> Self {
+        let seed = fixed_seed.unwrap_or_else(rand::random);
+
+        Self {
+            fixed_seed,
+            seed,
+            streams: Self::seed_streams(seed),
+        }
+    }
+
+    /// Start a new run from the fixed seed or a fresh random one
+    pub fn reseed(&mut self) {
+        self.seed_with(self.fixed_seed.unwrap_or_else(rand::random));
+    }
+
+    pub fn seed_with(&mut self, seed: u64) {
+        self.seed = seed;
+        self.streams = Self::seed_streams(seed);
+    }
+
+    pub fn stream(&mut self, stream: RngStream) -> &mut StdRng {
+        &mut self.streams[stream as usize]
+    }
+
+    /// Parse a `--seed` value, shared by the game and headless mode
+    pub fn parse_seed(seed: &str) -> Result<u64, String> {
+        seed.parse().map_err(|_| {
+            format!(
+                "--seed needs a whole number from 0 to {}, got {}",
+                u64::MAX,
+                seed
+            )
+        })
+    }
+
+    fn seed_streams(seed: u64) -> [StdRng; RngStream::ALL.len()] {
+        RngStream::ALL.map(|stream| StdRng::seed_from_u64(seed ^ stream as u64))
+    }
+}
+
+impl Default for GameRng {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
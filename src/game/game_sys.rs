@@ -1,37 +1,94 @@
 use bevy::{
     prelude::*,
-    window::{CursorGrabMode, PrimaryWindow},
+    window::{CursorGrabMode, PrimaryWindow, WindowFocused},
 };
 
-use crate::{gamepad::gamepad_rcs::MyGamepad, AppState};
+use crate::{
+    actions::actions_res::{Action, InputActions},
+    AppState, PauseState,
+};
 
-use super::{game_cmps::*, game_evs::*, game_res::*};
+use super::{
+    bot::bot_res::Demo,
+    game_cmps::*,
+    game_evs::*,
+    game_res::{GameRng, GameTime},
+};
 
-pub fn exit_game(
-    btns: Res<Input<GamepadButton>>,
-    keys: Res<Input<KeyCode>>,
-    my_gamepad: Option<Res<MyGamepad>>,
-    mut next_app_state: ResMut<NextState<AppState>>,
-    mut game_time: ResMut<GameTime>,
+/// Pause or resume the game on the pause action
+pub fn toggle_pause(
+    actions: Res<InputActions>,
+    pause_state: Res<State<PauseState>>,
+    mut next_pause_state: ResMut<NextState<PauseState>>,
+) {
+    if actions.just_pressed(Action::Pause) {
+        match pause_state.get() {
+            PauseState::Running => next_pause_state.set(PauseState::Paused),
+            PauseState::Paused => next_pause_state.set(PauseState::Running),
+        }
+    }
+}
+
+/// Pause the game when the window loses focus
+pub fn pause_on_focus_lost(
+    mut focus_evr: EventReader<WindowFocused>,
+    mut next_pause_state: ResMut<NextState<PauseState>>,
 ) {
-    // gamepad
-    let gamepad_input = my_gamepad
-        .map(|gp| btns.just_pressed(GamepadButton::new(gp.gamepad, GamepadButtonType::Start)))
-        .unwrap_or(false);
-
-    if gamepad_input || keys.just_pressed(KeyCode::Escape) {
-        next_app_state.set(AppState::MainMenu);
-        game_time.0.reset(); // reset stopwatch
+    for ev in focus_evr.iter() {
+        if !ev.focused {
+            next_pause_state.set(PauseState::Paused);
+        }
     }
 }
 
-/// Change state to GameOver when GameOver event is fired
+/// Leaving the game always leaves the pause menu too
+pub fn unpause_game(mut next_pause_state: ResMut<NextState<PauseState>>) {
+    next_pause_state.set(PauseState::Running);
+}
+
+/// Restart passes straight back into Game, which re-runs all the OnEnter setup
+pub fn restart_game(mut next_app_state: ResMut<NextState<AppState>>) {
+    next_app_state.set(AppState::Game);
+}
+
+pub fn tick_game_time(time: Res<FixedTime>, mut game_time: ResMut<GameTime>) {
+    game_time.0.tick(time.period);
+}
+
+/// reset the game time to zero whenever game starts
+pub fn reset_game_time(mut game_time: ResMut<GameTime>) {
+    game_time.0.reset();
+}
+
+/// Every run starts from the `--seed` seed or a fresh random one
+pub fn seed_game_rng(mut game_rng: ResMut<GameRng>) {
+    game_rng.reseed();
+}
+
+/// Gameplay ticks run while a game is in progress and not paused
+/// They stop as soon as the game is about to be left, so no ticks run after game over
+pub fn game_ticking(
+    app_state: Res<State<AppState>>,
+    pause_state: Res<State<PauseState>>,
+    next_app_state: Res<NextState<AppState>>,
+) -> bool {
+    *app_state.get() == AppState::Game
+        && *pause_state.get() == PauseState::Running
+        && next_app_state.0.is_none()
+}
+
+/// Change state to GameOver when GameOver event is fired, a demo goes back to the main menu
 pub fn game_over(
+    demo: Option<Res<Demo>>,
     mut next_app_state: ResMut<NextState<AppState>>,
     mut game_over_evr: EventReader<GameOver>,
 ) {
     for _ev in game_over_evr.iter() {
-        next_app_state.set(AppState::GameOver);
+        if demo.is_some() {
+            next_app_state.set(AppState::MainMenu);
+        } else {
+            next_app_state.set(AppState::GameOver);
+        }
     }
 }
 
@@ -44,7 +101,7 @@ pub fn despawn_game(mut cmds: Commands, all_q: Query<Entity, With<Game>>) {
 
 /// un-hides the cursor when exiting game state
 pub fn show_cursor(mut window_q: Query<&mut Window, With<PrimaryWindow>>) {
-    let mut window = window_q.get_single_mut().unwrap();
+    let Ok(mut window) = window_q.get_single_mut() else { return };
     window.cursor.grab_mode = CursorGrabMode::None;
     window.cursor.visible = true;
 }
@@ -1,5 +1,11 @@
 use bevy::prelude::*;
 
+use super::WAVE_BANNER_DURATION;
+
+/// Container of a player's HUD, covering the part of the window their camera draws to
+#[derive(Component)]
+pub struct PlayerHud;
+
 #[derive(Component)]
 pub struct HealthBarContainer;
 
@@ -20,3 +26,36 @@ pub struct GameTimeDisplayTxt;
 
 #[derive(Component)]
 pub struct KillCountTxt;
+
+#[derive(Component)]
+pub struct BeatIndicator;
+
+#[derive(Component)]
+pub struct WaveTxt;
+
+#[derive(Component)]
+pub struct AmmoTxt;
+
+#[derive(Component)]
+pub struct CashTxt;
+
+/// Active status effects of a player with their time left
+#[derive(Component)]
+pub struct EffectsTxt;
+
+/// Shown in the middle of a downed player's HUD with their revive progress
+#[derive(Component)]
+pub struct DownedTxt;
+
+#[derive(Component)]
+pub struct WaveBanner {
+    pub duration: Timer,
+}
+
+impl Default for WaveBanner {
+    fn default() -> Self {
+        WaveBanner {
+            duration: Timer::from_seconds(WAVE_BANNER_DURATION, TimerMode::Once),
+        }
+    }
+}
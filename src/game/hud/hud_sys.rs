@@ -1,13 +1,63 @@
 use bevy::prelude::*;
 
-use super::hud_cmps::*;
+use super::{hud_cmps::*, *};
 use crate::game::{
+    camera::split_screen_rect,
+    config::config_res::GameConfig,
+    effects::effects_cmps::StatusEffects,
     game_cmps::{Game, Hp},
     game_res::GameTime,
-    player::{player_cmps::*, player_res::KillCount},
+    music::{music_evs::Beat, music_res::BeatClock},
+    player::{player_cmps::*, player_res::Party},
+    waves::{
+        waves_evs::{WaveCleared, WaveStarted},
+        waves_res::{WaveDirector, WavePhase},
+    },
+    weapons::weapons_cmps::Arsenal,
 };
 
-pub fn spawn_crosshairs(mut cmds: Commands, assets: Res<AssetServer>) {
+/// A HUD for every player, laid out over the part of the window their camera draws to
+pub fn spawn_player_huds(mut cmds: Commands, assets: Res<AssetServer>, party: Res<Party>) {
+    let players = party.0.len();
+
+    for idx in 0..players {
+        let id = PlayerId(idx);
+        let rect = split_screen_rect(idx, players);
+
+        let container = (
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    left: Val::Percent(rect.min.x * 100.0),
+                    top: Val::Percent(rect.min.y * 100.0),
+                    width: Val::Percent(rect.width() * 100.0),
+                    height: Val::Percent(rect.height() * 100.0),
+                    ..default()
+                },
+                ..default()
+            },
+            PlayerHud,
+            id,
+            Name::new(format!("Player {} HUD", idx + 1)),
+            Game,
+        );
+
+        cmds.spawn(container).with_children(|parent| {
+            spawn_crosshairs(parent, &assets);
+            spawn_beat_indicator(parent);
+            spawn_health_bar(parent, &assets, id);
+            spawn_stamina_bar(parent, &assets, id);
+            spawn_kill_count(parent, &assets, id);
+            spawn_wave_display(parent, &assets);
+            spawn_ammo_display(parent, &assets, id);
+            spawn_cash_display(parent, &assets, id);
+            spawn_effects_display(parent, &assets, id);
+            spawn_downed_display(parent, &assets, id);
+        });
+    }
+}
+
+fn spawn_crosshairs(parent: &mut ChildBuilder, assets: &Res<AssetServer>) {
     let crosshairs = (
         ImageBundle {
             image: assets.load("imgs/crosshairs.png").into(),
@@ -30,10 +80,54 @@ pub fn spawn_crosshairs(mut cmds: Commands, assets: Res<AssetServer>) {
         Name::new("Crosshairs"),
     );
 
-    cmds.spawn(crosshairs);
+    parent.spawn(crosshairs);
 }
 
-pub fn spawn_kill_count(mut cmds: Commands, assets: Res<AssetServer>) {
+/// dot below the crosshairs that pulses with the beat and lights up while shots are on-beat
+fn spawn_beat_indicator(parent: &mut ChildBuilder) {
+    let indicator = (
+        NodeBundle {
+            background_color: BEAT_INDICATOR_COLOR.into(),
+            style: Style {
+                position_type: PositionType::Absolute,
+                left: Val::Percent(50.0),
+                top: Val::Percent(58.0),
+                width: Val::Px(BEAT_INDICATOR_SIZE),
+                height: Val::Px(BEAT_INDICATOR_SIZE),
+                ..default()
+            },
+            ..default()
+        },
+        BeatIndicator,
+        Name::new("Beat Indicator"),
+    );
+
+    parent.spawn(indicator);
+}
+
+pub fn update_beat_indicator(
+    beat_clock: Res<BeatClock>,
+    config: Res<GameConfig>,
+    mut indicator_q: Query<(&mut Style, &mut BackgroundColor), With<BeatIndicator>>,
+) {
+    // largest right on the beat, shrinking until the next one
+    let size = BEAT_INDICATOR_SIZE * (1.0 + BEAT_INDICATOR_PULSE * (1.0 - beat_clock.phase()));
+    let color = if beat_clock.is_on_beat(config.rhythm.on_beat_window) {
+        BEAT_INDICATOR_COLOR_ON_BEAT
+    } else {
+        BEAT_INDICATOR_COLOR
+    };
+
+    for (mut style, mut background_clr) in indicator_q.iter_mut() {
+        style.width = Val::Px(size);
+        style.height = Val::Px(size);
+        style.margin = UiRect::left(Val::Px(-size / 2.0));
+
+        *background_clr = color.into();
+    }
+}
+
+fn spawn_kill_count(parent: &mut ChildBuilder, assets: &Res<AssetServer>, id: PlayerId) {
     let txt = TextBundle {
         text: Text::from_section(
             "",
@@ -52,16 +146,197 @@ pub fn spawn_kill_count(mut cmds: Commands, assets: Res<AssetServer>) {
         ..default()
     };
 
-    cmds.spawn((txt, KillCountTxt, Game));
+    parent.spawn((txt, KillCountTxt, id));
+}
+
+pub fn update_kill_count(
+    player_q: Query<(&Kills, &PlayerId), With<Player>>,
+    mut txt_q: Query<(&mut Text, &PlayerId), With<KillCountTxt>>,
+) {
+    for (mut txt, id) in txt_q.iter_mut() {
+        if let Some((kills, _)) = player_q.iter().find(|(_, player_id)| *player_id == id) {
+            txt.sections[0].value = format!("Kills: {}", kills.0);
+        }
+    }
+}
+
+fn spawn_wave_display(parent: &mut ChildBuilder, assets: &Res<AssetServer>) {
+    let txt = TextBundle {
+        text: Text::from_section(
+            "",
+            TextStyle {
+                font: assets.load("fonts/PermanentMarker-Regular.ttf"),
+                font_size: 25.0,
+                color: Color::WHITE,
+            },
+        ),
+        style: Style {
+            position_type: PositionType::Absolute,
+            left: Val::Percent(1.2),
+            top: Val::Percent(10.25),
+            ..default()
+        },
+        ..default()
+    };
+
+    parent.spawn((txt, WaveTxt, Name::new("Wave Text")));
+}
+
+/// show the current wave, or a countdown to the next one during intermissions
+pub fn update_wave_display(
+    director: Res<WaveDirector>,
+    mut txt_q: Query<&mut Text, With<WaveTxt>>,
+) {
+    let wave = match director.phase {
+        WavePhase::Active => format!("Wave: {}", director.wave),
+        WavePhase::Intermission => {
+            let time_left = director.intermission.remaining_secs().ceil();
+            format!("Next Wave: {}", time_left)
+        }
+    };
+
+    for mut txt in txt_q.iter_mut() {
+        txt.sections[0].value = wave.clone();
+    }
+}
+
+fn spawn_ammo_display(parent: &mut ChildBuilder, assets: &Res<AssetServer>, id: PlayerId) {
+    let txt = TextBundle {
+        text: Text::from_section(
+            "",
+            TextStyle {
+                font: assets.load("fonts/PermanentMarker-Regular.ttf"),
+                font_size: 25.0,
+                color: Color::WHITE,
+            },
+        ),
+        style: Style {
+            position_type: PositionType::Absolute,
+            right: Val::Percent(1.2),
+            top: Val::Percent(7.5),
+            ..default()
+        },
+        ..default()
+    };
+
+    parent.spawn((txt, AmmoTxt, id, Name::new("Ammo Text")));
+}
+
+/// show the equipped weapon with its magazine and reserve ammo
+pub fn update_ammo_display(
+    arsenal_q: Query<(&Arsenal, &PlayerId), With<Player>>,
+    mut txt_q: Query<(&mut Text, &PlayerId), With<AmmoTxt>>,
+) {
+    for (mut txt, id) in txt_q.iter_mut() {
+        let Some((arsenal, _)) = arsenal_q.iter().find(|(_, player_id)| *player_id == id) else {
+            continue;
+        };
+
+        let weapon = arsenal.current();
+
+        let reserve = match weapon.reserve {
+            Some(reserve) => reserve.to_string(),
+            None => "--".to_string(),
+        };
+
+        txt.sections[0].value = if weapon.is_reloading() {
+            format!("{}: Reloading...", weapon.stats.name)
+        } else {
+            format!("{}: {} / {}", weapon.stats.name, weapon.magazine, reserve)
+        };
+    }
+}
+
+fn spawn_cash_display(parent: &mut ChildBuilder, assets: &Res<AssetServer>, id: PlayerId) {
+    let txt = TextBundle {
+        text: Text::from_section(
+            "",
+            TextStyle {
+                font: assets.load("fonts/PermanentMarker-Regular.ttf"),
+                font_size: 25.0,
+                color: Color::WHITE,
+            },
+        ),
+        style: Style {
+            position_type: PositionType::Absolute,
+            right: Val::Percent(1.2),
+            top: Val::Percent(10.25),
+            ..default()
+        },
+        ..default()
+    };
+
+    parent.spawn((txt, CashTxt, id, Name::new("Cash Text")));
 }
 
-pub fn update_kill_count(kills: Res<KillCount>, mut txt_q: Query<&mut Text, With<KillCountTxt>>) {
-    if let Ok(mut txt) = txt_q.get_single_mut() {
-        txt.sections[0].value = format!("Kills: {}", kills.0);
+pub fn update_cash_display(
+    player_q: Query<(&Cash, &PlayerId), With<Player>>,
+    mut txt_q: Query<(&mut Text, &PlayerId), With<CashTxt>>,
+) {
+    for (mut txt, id) in txt_q.iter_mut() {
+        if let Some((cash, _)) = player_q.iter().find(|(_, player_id)| *player_id == id) {
+            txt.sections[0].value = format!("Cash: {}", cash.0);
+        }
     }
 }
 
-pub fn spawn_health_bar(mut cmds: Commands, assets: Res<AssetServer>) {
+/// announce wave starts and clears in the middle of the screen
+pub fn spawn_wave_banner(
+    mut cmds: Commands,
+    assets: Res<AssetServer>,
+    mut wave_started_evr: EventReader<WaveStarted>,
+    mut wave_cleared_evr: EventReader<WaveCleared>,
+    banner_q: Query<Entity, With<WaveBanner>>,
+) {
+    let started = wave_started_evr.iter().map(|ev| format!("Wave {}", ev.0));
+    let cleared = wave_cleared_evr
+        .iter()
+        .map(|ev| format!("Wave {} Cleared!", ev.0));
+
+    for msg in cleared.chain(started) {
+        // only ever show the latest banner
+        for ent in banner_q.iter() {
+            cmds.entity(ent).despawn_recursive();
+        }
+
+        let txt = TextBundle {
+            text: Text::from_section(
+                msg,
+                TextStyle {
+                    font: assets.load("fonts/PermanentMarker-Regular.ttf"),
+                    font_size: 60.0,
+                    color: Color::RED,
+                },
+            ),
+            style: Style {
+                position_type: PositionType::Absolute,
+                align_self: AlignSelf::Center,
+                left: Val::Percent(40.0),
+                top: Val::Percent(25.0),
+                ..default()
+            },
+            ..default()
+        };
+
+        cmds.spawn((txt, WaveBanner::default(), Name::new("Wave Banner"), Game));
+    }
+}
+
+pub fn despawn_wave_banner(
+    mut cmds: Commands,
+    time: Res<Time>,
+    mut banner_q: Query<(Entity, &mut WaveBanner)>,
+) {
+    for (ent, mut banner) in banner_q.iter_mut() {
+        banner.duration.tick(time.delta());
+
+        if banner.duration.finished() {
+            cmds.entity(ent).despawn_recursive();
+        }
+    }
+}
+
+fn spawn_health_bar(parent: &mut ChildBuilder, assets: &Res<AssetServer>, id: PlayerId) {
     let container = create_container(
         Color::BLACK,
         UiRect::left(Val::Percent(1.0)),
@@ -76,16 +351,17 @@ pub fn spawn_health_bar(mut cmds: Commands, assets: Res<AssetServer>) {
         Val::Percent(99.0),
     );
 
-    let txt = create_txt(&assets);
+    let txt = create_txt(assets);
 
-    cmds.spawn((container, HealthBarContainer, Name::new("Health Bar"), Game))
+    parent
+        .spawn((container, HealthBarContainer, Name::new("Health Bar")))
         .with_children(|parent| {
             parent.spawn(fill);
-            parent.spawn((txt, HealthBarTxt, Name::new("Health Bar Text")));
+            parent.spawn((txt, HealthBarTxt, id, Name::new("Health Bar Text")));
         });
 }
 
-pub fn spawn_stamina_bar(mut cmds: Commands, assets: Res<AssetServer>) {
+fn spawn_stamina_bar(parent: &mut ChildBuilder, assets: &Res<AssetServer>, id: PlayerId) {
     let container = create_container(
         Color::BLACK,
         UiRect::left(Val::Percent(89.0)),
@@ -100,42 +376,157 @@ pub fn spawn_stamina_bar(mut cmds: Commands, assets: Res<AssetServer>) {
         Val::Percent(99.0),
     );
 
-    let txt = create_txt(&assets);
+    let txt = create_txt(assets);
 
-    cmds.spawn((
-        container,
-        StaminaBarContainer,
-        Name::new("Stamina Bar"),
-        Game,
-    ))
-    .with_children(|parent| {
-        parent.spawn(fill);
-        parent.spawn((txt, StaminaBarTxt, Name::new("Stamina Bar Text")));
-    });
+    parent
+        .spawn((container, StaminaBarContainer, Name::new("Stamina Bar")))
+        .with_children(|parent| {
+            parent.spawn(fill);
+            parent.spawn((txt, StaminaBarTxt, id, Name::new("Stamina Bar Text")));
+        });
 }
 
 pub fn update_stamina_bar(
-    mut stamina_q: Query<&mut Text, With<StaminaBarTxt>>,
-    player_q: Query<&Stamina, With<Player>>,
+    mut stamina_q: Query<(&mut Text, &PlayerId), With<StaminaBarTxt>>,
+    player_q: Query<(&Stamina, &PlayerId), With<Player>>,
 ) {
-    if let Ok(mut txt) = stamina_q.get_single_mut() {
-        if let Ok(stamina) = player_q.get_single() {
+    for (mut txt, id) in stamina_q.iter_mut() {
+        if let Some((stamina, _)) = player_q.iter().find(|(_, player_id)| *player_id == id) {
             txt.sections[0].value = stamina.value.round().to_string();
         }
     }
 }
 
 pub fn update_health_bar(
-    mut stamina_q: Query<&mut Text, With<HealthBarTxt>>,
-    player_q: Query<&Hp, With<Player>>,
+    mut health_q: Query<(&mut Text, &PlayerId), With<HealthBarTxt>>,
+    player_q: Query<(&Hp, &PlayerId), With<Player>>,
 ) {
-    if let Ok(mut txt) = stamina_q.get_single_mut() {
-        if let Ok(hp) = player_q.get_single() {
+    for (mut txt, id) in health_q.iter_mut() {
+        if let Some((hp, _)) = player_q.iter().find(|(_, player_id)| *player_id == id) {
             txt.sections[0].value = hp.value.round().to_string();
         }
     }
 }
 
+fn spawn_effects_display(parent: &mut ChildBuilder, assets: &Res<AssetServer>, id: PlayerId) {
+    let txt = TextBundle {
+        text: Text::from_section(
+            "",
+            TextStyle {
+                font: assets.load("fonts/PermanentMarker-Regular.ttf"),
+                font_size: 25.0,
+                color: Color::WHITE,
+            },
+        ),
+        style: Style {
+            display: Display::None,
+            position_type: PositionType::Absolute,
+            left: Val::Percent(1.2),
+            top: Val::Percent(13.0),
+            ..default()
+        },
+        ..default()
+    };
+
+    parent.spawn((txt, Name::new("Effects Text"), EffectsTxt, id));
+}
+
+/// List the player's active effects with the seconds they have left, one per line
+pub fn update_effects_display(
+    config: Res<GameConfig>,
+    player_q: Query<(&StatusEffects, &PlayerId), With<Player>>,
+    mut txt_q: Query<(&mut Text, &mut Style, &PlayerId), With<EffectsTxt>>,
+) {
+    for (mut txt, mut style, id) in txt_q.iter_mut() {
+        let effects = player_q
+            .iter()
+            .find(|(_, player_id)| *player_id == id)
+            .map(|(effects, _)| effects);
+
+        let Some(effects) = effects.filter(|effects| !effects.0.is_empty()) else {
+            style.display = Display::None;
+            continue;
+        };
+
+        style.display = Display::Flex;
+
+        txt.sections[0].value = effects
+            .0
+            .iter()
+            .map(|effect| {
+                let name = &config.effect(effect.kind).name;
+                let time_left = effect.timer.remaining().as_secs();
+                if effect.stacks > 1 {
+                    format!("{} x{}: {}", name, effect.stacks, time_left)
+                } else {
+                    format!("{}: {}", name, time_left)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+    }
+}
+
+fn spawn_downed_display(parent: &mut ChildBuilder, assets: &Res<AssetServer>, id: PlayerId) {
+    let container = NodeBundle {
+        style: Style {
+            display: Display::None,
+            justify_content: JustifyContent::Center,
+            position_type: PositionType::Absolute,
+            top: Val::Percent(35.0),
+            width: Val::Percent(100.0),
+            ..default()
+        },
+        ..default()
+    };
+
+    let txt = TextBundle::from_section(
+        "",
+        TextStyle {
+            font: assets.load("fonts/PermanentMarker-Regular.ttf"),
+            font_size: 40.0,
+            color: Color::RED,
+        },
+    );
+
+    parent
+        .spawn((container, DownedTxt, id, Name::new("Downed Display")))
+        .with_children(|parent| {
+            parent.spawn(txt);
+        });
+}
+
+/// Tell downed players to wait for a teammate and show how far along the revive is
+pub fn update_downed_display(
+    player_q: Query<(Option<&Downed>, &PlayerId), With<Player>>,
+    mut display_q: Query<(&mut Style, &Children, &PlayerId), With<DownedTxt>>,
+    mut txt_q: Query<&mut Text>,
+) {
+    for (mut style, children, id) in display_q.iter_mut() {
+        let downed = player_q
+            .iter()
+            .find(|(_, player_id)| *player_id == id)
+            .and_then(|(downed, _)| downed);
+
+        let Some(downed) = downed else {
+            style.display = Display::None;
+            continue;
+        };
+
+        style.display = Display::Flex;
+
+        for child in children.iter() {
+            if let Ok(mut txt) = txt_q.get_mut(*child) {
+                txt.sections[0].value = if downed.revive.elapsed_secs() > 0.0 {
+                    format!("Reviving... {:.0}%", downed.revive.percent() * 100.0)
+                } else {
+                    "Downed! Get a teammate over here".to_string()
+                };
+            }
+        }
+    }
+}
+
 pub fn spawn_time_display(mut cmds: Commands, assets: Res<AssetServer>) {
     let container = (
         NodeBundle {
@@ -158,7 +549,7 @@ pub fn spawn_time_display(mut cmds: Commands, assets: Res<AssetServer>) {
             TextStyle {
                 color: Color::WHITE,
                 font: assets.load("fonts/PermanentMarker-Regular.ttf"),
-                font_size: 40.0,
+                font_size: GAME_TIME_FONT_SIZE,
             },
         ),
         GameTimeDisplayTxt,
@@ -172,20 +563,32 @@ pub fn spawn_time_display(mut cmds: Commands, assets: Res<AssetServer>) {
 
 pub fn update_game_time_display(
     mut time_display_q: Query<&mut Text, With<GameTimeDisplayTxt>>,
-    mut game_time: ResMut<GameTime>,
-    time: Res<Time>,
+    game_time: Res<GameTime>,
 ) {
-    game_time.0.tick(time.delta());
-
     if let Ok(mut txt) = time_display_q.get_single_mut() {
         let time = game_time.0.elapsed().as_secs_f32();
         txt.sections[0].value = format!("{:.2}", time);
     }
 }
 
-/// reset the game time to zero whenever game starts
-pub fn reset_game_time(mut game_time: ResMut<GameTime>) {
-    game_time.0.reset();
+/// bump the game time display on every beat and shrink it back in between
+pub fn pulse_game_time_display(
+    time: Res<Time>,
+    mut beat_evr: EventReader<Beat>,
+    mut time_display_q: Query<&mut Text, With<GameTimeDisplayTxt>>,
+) {
+    let on_beat = beat_evr.iter().count() > 0;
+
+    if let Ok(mut txt) = time_display_q.get_single_mut() {
+        let style = &mut txt.sections[0].style;
+
+        if on_beat {
+            style.font_size = GAME_TIME_FONT_SIZE + BEAT_PULSE_SIZE;
+        } else if style.font_size > GAME_TIME_FONT_SIZE {
+            style.font_size -= BEAT_PULSE_DECAY * time.delta_seconds();
+            style.font_size = style.font_size.max(GAME_TIME_FONT_SIZE);
+        }
+    }
 }
 
 fn create_txt(assets: &Res<AssetServer>) -> TextBundle {
@@ -5,7 +5,19 @@ mod hud_sys;
 
 use hud_sys::*;
 
-use crate::AppState;
+use crate::{
+    game::{GameplaySet, RunSetupSet},
+    AppState,
+};
+
+pub const GAME_TIME_FONT_SIZE: f32 = 40.0;
+pub const BEAT_PULSE_SIZE: f32 = 8.0;
+pub const BEAT_PULSE_DECAY: f32 = 40.0;
+pub const BEAT_INDICATOR_SIZE: f32 = 10.0;
+pub const BEAT_INDICATOR_PULSE: f32 = 1.0;
+pub const BEAT_INDICATOR_COLOR: Color = Color::rgba(1.0, 1.0, 1.0, 0.35);
+pub const BEAT_INDICATOR_COLOR_ON_BEAT: Color = Color::GOLD;
+pub const WAVE_BANNER_DURATION: f32 = 2.5;
 
 pub struct HudPlugin;
 
@@ -13,14 +25,7 @@ impl Plugin for HudPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
             OnEnter(AppState::Game),
-            (
-                spawn_crosshairs,
-                spawn_health_bar,
-                spawn_stamina_bar,
-                spawn_time_display,
-                reset_game_time,
-                spawn_kill_count,
-            ),
+            (spawn_player_huds.after(RunSetupSet), spawn_time_display),
         )
         .add_systems(
             Update,
@@ -28,9 +33,18 @@ impl Plugin for HudPlugin {
                 update_stamina_bar,
                 update_health_bar,
                 update_game_time_display,
+                pulse_game_time_display,
+                update_beat_indicator,
                 update_kill_count,
+                update_wave_display,
+                spawn_wave_banner,
+                despawn_wave_banner,
+                update_ammo_display,
+                update_cash_display,
+                update_effects_display,
+                update_downed_display,
             )
-                .run_if(in_state(AppState::Game)),
+                .in_set(GameplaySet),
         );
     }
 }
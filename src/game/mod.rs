@@ -1,35 +1,89 @@
-use bevy::prelude::*;
-use bevy_rapier3d::prelude::{NoUserData, RapierPhysicsPlugin};
+use bevy::{ecs::schedule::ExecutorKind, prelude::*};
+use bevy_rapier3d::prelude::{
+    NoUserData, PhysicsSet, RapierConfiguration, RapierPhysicsPlugin, TimestepMode,
+};
 use bevy_rapier3d::render::RapierDebugRenderPlugin;
 use bevy_third_person_camera::ThirdPersonCameraPlugin;
 
+pub mod bot;
 pub mod camera;
+pub mod config;
+pub mod crowd;
+pub mod effects;
 pub mod enemy;
 pub mod game_cmps;
 pub mod game_evs;
 pub mod game_res;
-mod game_sys;
+pub mod game_sys;
 pub mod hud;
 pub mod music;
+pub mod nav;
 pub mod player;
 pub mod powerups;
 pub mod projectile;
+pub mod replay;
+pub mod stats;
+pub mod waves;
+pub mod weapons;
 pub mod world;
 
+use bot::{bot_res::Demo, BotPlugin};
 use camera::CameraPlugin;
+use config::ConfigPlugin;
+use crowd::CrowdPlugin;
+use effects::EffectsPlugin;
 use enemy::EnemyPlugin;
 use game_evs::*;
 use game_res::*;
 use game_sys::*;
 use hud::HudPlugin;
 use music::MusicPlugin;
+use nav::NavPlugin;
 use player::PlayerPlugin;
 use powerups::PowerUpsPlugin;
 use projectile::ProjectilePlugin;
+use replay::ReplayPlugin;
+use stats::StatsPlugin;
+use waves::WavesPlugin;
+use weapons::WeaponsPlugin;
 use world::WorldPlugin;
 
 use crate::debug::debug_res::EnableDebugMode;
-use crate::AppState;
+use crate::{AppState, PauseState, SettingsState};
+
+/// gameplay ticks per second, the simulation runs at this rate no matter the frame rate
+pub const TICK_RATE: f32 = 60.0;
+
+/// Systems that only run while a game is in progress and not paused
+/// In FixedUpdate these are the simulation, in Update everything that only presents it
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GameplaySet;
+
+/// Start of every gameplay tick, before the rest of the simulation
+/// Swaps the gameplay event buffers and samples the input of every player
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TickStartSet;
+
+/// Systems that set up a run on OnEnter(AppState::Game) before anything is spawned
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RunSetupSet;
+
+pub trait AddTickEvent {
+    /// Like `add_event`, but the event buffers swap every gameplay tick instead of every frame
+    /// so frames without a tick can't drop events before the simulation reads them
+    fn add_tick_event<T: Event>(&mut self) -> &mut Self;
+}
+
+impl AddTickEvent for App {
+    fn add_tick_event<T: Event>(&mut self) -> &mut Self {
+        if !self.world.contains_resource::<Events<T>>() {
+            self.init_resource::<Events<T>>()
+                .add_systems(FixedUpdate, Events::<T>::update_system.in_set(TickStartSet));
+        }
+        self
+    }
+}
+
 pub struct GamePlugin;
 
 impl Plugin for GamePlugin {
@@ -41,28 +95,118 @@ impl Plugin for GamePlugin {
             .map(|debug| debug.0)
             .unwrap_or(false);
 
+        // headless runs leave out everything that only draws the game
+        let is_headless = app.world.contains_resource::<Headless>();
+
+        // a single thread keeps the order of the simulation the same on every run
+        app.edit_schedule(FixedUpdate, |schedule| {
+            schedule.set_executor_kind(ExecutorKind::SingleThreaded);
+        });
+
         app.init_resource::<GameTime>()
-            .add_event::<GameOver>()
+            .init_resource::<GameMode>()
+            .init_resource::<GameRng>()
+            .insert_resource(FixedTime::new_from_secs(1.0 / TICK_RATE))
+            .insert_resource(RapierConfiguration {
+                timestep_mode: TimestepMode::Fixed {
+                    dt: 1.0 / TICK_RATE,
+                    substeps: 1,
+                },
+                ..default()
+            })
+            .add_tick_event::<GameOver>()
+            .configure_set(
+                Update,
+                GameplaySet
+                    .run_if(in_state(AppState::Game))
+                    .run_if(in_state(PauseState::Running))
+                    .run_if(not(resource_exists::<Headless>())),
+            )
+            .configure_sets(
+                FixedUpdate,
+                (
+                    TickStartSet,
+                    GameplaySet,
+                    PhysicsSet::SyncBackend,
+                    PhysicsSet::SyncBackendFlush,
+                    PhysicsSet::StepSimulation,
+                    PhysicsSet::Writeback,
+                )
+                    .chain(),
+            )
+            .configure_set(FixedUpdate, TickStartSet.run_if(game_ticking))
+            .configure_set(FixedUpdate, GameplaySet.run_if(game_ticking))
+            .configure_set(FixedUpdate, PhysicsSet::SyncBackend.run_if(game_ticking))
+            .configure_set(
+                FixedUpdate,
+                PhysicsSet::SyncBackendFlush.run_if(game_ticking),
+            )
+            .configure_set(FixedUpdate, PhysicsSet::StepSimulation.run_if(game_ticking))
+            .configure_set(FixedUpdate, PhysicsSet::Writeback.run_if(game_ticking))
+            .add_systems(FixedUpdate, physics_systems())
+            .add_plugins((ConfigPlugin, ReplayPlugin))
             .add_plugins((
-                CameraPlugin,
+                BotPlugin,
+                CrowdPlugin,
+                EffectsPlugin,
                 EnemyPlugin,
-                HudPlugin,
                 MusicPlugin,
+                NavPlugin,
                 PowerUpsPlugin,
                 PlayerPlugin,
                 ProjectilePlugin,
-                RapierPhysicsPlugin::<NoUserData>::default(),
-                RapierDebugRenderPlugin {
-                    enabled: is_debug_mode,
-                    ..default()
-                },
-                ThirdPersonCameraPlugin,
+                // physics steps with the gameplay ticks, see physics_systems
+                RapierPhysicsPlugin::<NoUserData>::default().with_default_system_setup(false),
+                StatsPlugin,
+                WavesPlugin,
+                WeaponsPlugin,
                 WorldPlugin,
             ))
             .add_systems(
                 Update,
-                (exit_game, game_over).run_if(in_state(AppState::Game)),
+                (
+                    toggle_pause
+                        .run_if(in_state(AppState::Game))
+                        .run_if(in_state(SettingsState::Closed)),
+                    pause_on_focus_lost.run_if(in_state(AppState::Game)),
+                )
+                    // the demo is left with any button instead
+                    .run_if(not(resource_exists::<Demo>())),
             )
-            .add_systems(OnExit(AppState::Game), (despawn_game, show_cursor));
+            .add_systems(FixedUpdate, (tick_game_time, game_over).in_set(GameplaySet))
+            .add_systems(
+                OnEnter(AppState::Game),
+                (seed_game_rng.in_set(RunSetupSet), reset_game_time),
+            )
+            .add_systems(OnEnter(AppState::Restart), restart_game)
+            .add_systems(
+                OnExit(AppState::Game),
+                (despawn_game, show_cursor, unpause_game),
+            );
+
+        if !is_headless {
+            app.add_plugins((
+                CameraPlugin,
+                HudPlugin,
+                RapierDebugRenderPlugin {
+                    enabled: is_debug_mode,
+                    ..default()
+                },
+                ThirdPersonCameraPlugin,
+            ));
+        }
     }
 }
+
+/// Rapier's systems, stepped in FixedUpdate after the rest of the simulation
+fn physics_systems() -> bevy::ecs::schedule::SystemConfigs {
+    type Physics = RapierPhysicsPlugin<NoUserData>;
+
+    (
+        Physics::get_systems(PhysicsSet::SyncBackend).in_set(PhysicsSet::SyncBackend),
+        Physics::get_systems(PhysicsSet::SyncBackendFlush).in_set(PhysicsSet::SyncBackendFlush),
+        Physics::get_systems(PhysicsSet::StepSimulation).in_set(PhysicsSet::StepSimulation),
+        Physics::get_systems(PhysicsSet::Writeback).in_set(PhysicsSet::Writeback),
+    )
+        .into_configs()
+}
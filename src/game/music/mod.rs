@@ -1,20 +1,57 @@
 use bevy::prelude::*;
+use bevy_common_assets::ron::RonAssetPlugin;
 
 mod music_cmps;
+pub mod music_evs;
+pub mod music_res;
 mod music_sys;
 
+use music_evs::*;
+use music_res::*;
 use music_sys::*;
 
-use crate::{debug::debug_res::EnableDebugMode, AppState};
+use crate::{
+    debug::debug_res::EnableDebugMode,
+    game::{game_res::Headless, world::world_res::Levels, AddTickEvent, GameplaySet, RunSetupSet},
+    settings::settings_res::Settings,
+    AppState,
+};
+
+pub const DEFAULT_BPM: f32 = 120.0;
 
 pub struct MusicPlugin;
 
 impl Plugin for MusicPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(AppState::Game), stop_music.before(play_music))
+        app.add_plugins(RonAssetPlugin::<TrackInfo>::new(&["beat.ron"]))
+            .init_resource::<BeatClock>()
+            .init_resource::<TrackInfos>()
+            .init_resource::<CurrentTrack>()
+            .add_tick_event::<Beat>()
             .add_systems(
                 OnEnter(AppState::Game),
-                play_music.run_if(resource_equals(EnableDebugMode(false))),
-            );
+                (
+                    stop_music,
+                    pick_track,
+                    play_music
+                        .run_if(resource_equals(EnableDebugMode(false)))
+                        .run_if(not(resource_exists::<Headless>())),
+                    reset_beat_clock,
+                )
+                    .chain()
+                    .after(RunSetupSet),
+            )
+            .add_systems(
+                Update,
+                (
+                    load_track_infos.run_if(resource_changed::<Levels>()),
+                    sync_track_info,
+                ),
+            )
+            .add_systems(
+                Update,
+                apply_music_volume.run_if(resource_changed::<Settings>()),
+            )
+            .add_systems(FixedUpdate, tick_beat_clock.in_set(GameplaySet));
     }
 }
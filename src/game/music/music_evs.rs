@@ -0,0 +1,5 @@
+use bevy::prelude::Event;
+
+/// Fired on every beat of the current music track
+#[derive(Event)]
+pub struct Beat(pub u32);
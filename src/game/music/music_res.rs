@@ -0,0 +1,111 @@
+use bevy::{
+    prelude::*,
+    reflect::{TypePath, TypeUuid},
+    time::Stopwatch,
+    utils::HashMap,
+};
+use serde::Deserialize;
+
+use crate::game::world::world_res::Levels;
+
+use super::DEFAULT_BPM;
+
+/// Tempo info read from the `.beat.ron` sidecar file next to each music track
+/// bpm & offset are as heard, so at the track's speed
+#[derive(Deserialize, TypeUuid, TypePath)]
+#[uuid = "d7072b3d-b75b-4f88-bc3c-035566a9c60f"]
+pub struct TrackInfo {
+    pub bpm: f32,
+    /// seconds from the start of the track to the first beat
+    pub offset: f32,
+    /// recording to play without its extension, the track's own if not set
+    #[serde(default)]
+    pub source: Option<String>,
+    /// playback speed of the recording, lets levels share a recording at their own tempo
+    #[serde(default = "default_speed")]
+    pub speed: f32,
+}
+
+fn default_speed() -> f32 {
+    1.0
+}
+
+/// Tempo sidecars of every track in the playlists of the levels, by track path
+#[derive(Resource, Default)]
+pub struct TrackInfos(pub HashMap<String, Handle<TrackInfo>>);
+
+impl TrackInfos {
+    /// Whether every track of every level has its tempo requested
+    pub fn covers(&self, levels: &Levels) -> bool {
+        levels
+            .0
+            .iter()
+            .flat_map(|level| level.playlist.iter())
+            .all(|track| self.0.contains_key(track))
+    }
+}
+
+/// Track of the level's playlist playing in this run, without its extension
+#[derive(Resource, Default)]
+pub struct CurrentTrack(pub String);
+
+/// Gameplay clock that follows the beat of the music track
+#[derive(Resource)]
+pub struct BeatClock {
+    pub bpm: f32,
+    pub offset: f32,
+    pub elapsed: Stopwatch,
+    /// number of beats fired so far
+    pub beats: u32,
+}
+
+impl Default for BeatClock {
+    fn default() -> Self {
+        Self {
+            bpm: DEFAULT_BPM,
+            offset: 0.0,
+            elapsed: Stopwatch::new(),
+            beats: 0,
+        }
+    }
+}
+
+impl BeatClock {
+    /// length of a single beat in seconds
+    pub fn beat_duration(&self) -> f32 {
+        60.0 / self.bpm
+    }
+
+    /// beats elapsed since the first beat of the track (negative before the first beat)
+    pub fn position(&self) -> f32 {
+        (self.elapsed.elapsed_secs() - self.offset) / self.beat_duration()
+    }
+
+    /// progress through the current beat, from 0.0 on the beat to 1.0 right before the next one
+    pub fn phase(&self) -> f32 {
+        self.position().rem_euclid(1.0)
+    }
+
+    /// seconds to the closest beat, either before or after it
+    pub fn distance_to_beat(&self) -> f32 {
+        let position = self.position();
+        (position - position.round()).abs() * self.beat_duration()
+    }
+
+    pub fn is_on_beat(&self, window: f32) -> bool {
+        self.distance_to_beat() <= window
+    }
+
+    pub fn set_track(&mut self, track: &TrackInfo) {
+        self.bpm = track.bpm;
+        self.offset = track.offset;
+
+        // skip any beats the new tempo has already passed
+        self.beats = self.position().ceil().max(0.0) as u32;
+    }
+
+    pub fn reset(&mut self) {
+        self.elapsed.reset();
+        self.beats = 0;
+    }
+}
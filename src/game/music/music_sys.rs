@@ -1,12 +1,62 @@
-use bevy::prelude::*;
+use bevy::{
+    audio::{Volume, VolumeLevel},
+    prelude::*,
+};
 
-use super::music_cmps::Music;
+use rand::Rng;
+
+use crate::{
+    game::{
+        game_res::{GameRng, RngStream},
+        world::world_res::{Level, Levels},
+    },
+    settings::settings_res::Settings,
+};
+
+use super::{music_cmps::Music, music_evs::*, music_res::*};
+
+/// Pick the track of this run from the level's playlist, the beat clock follows its tempo
+pub fn pick_track(
+    level: Res<Level>,
+    track_infos: Res<TrackInfos>,
+    tracks: Res<Assets<TrackInfo>>,
+    mut current: ResMut<CurrentTrack>,
+    mut clock: ResMut<BeatClock>,
+    mut game_rng: ResMut<GameRng>,
+) {
+    let idx = game_rng
+        .stream(RngStream::Effects)
+        .gen_range(0..level.playlist.len());
+    current.0 = level.playlist[idx].clone();
+
+    if let Some(track) = track_infos.0.get(&current.0).and_then(|h| tracks.get(h)) {
+        clock.set_track(track);
+    }
+}
+
+/// Play the recording of the current track at the speed its sidecar asks for
+pub fn play_music(
+    mut cmds: Commands,
+    assets: Res<AssetServer>,
+    settings: Res<Settings>,
+    current: Res<CurrentTrack>,
+    track_infos: Res<TrackInfos>,
+    tracks: Res<Assets<TrackInfo>>,
+) {
+    let track = track_infos.0.get(&current.0).and_then(|h| tracks.get(h));
+    let source = track
+        .and_then(|track| track.source.as_ref())
+        .unwrap_or(&current.0);
+    let speed = track.map_or(1.0, |track| track.speed);
 
-pub fn play_music(mut cmds: Commands, assets: Res<AssetServer>) {
     cmds.spawn((
         AudioBundle {
-            source: assets.load(r"audio\music\tvs_story.ogg"),
-            ..default()
+            source: assets.load(format!("{}.ogg", source)),
+            settings: PlaybackSettings {
+                volume: Volume::Relative(VolumeLevel::new(settings.music_volume())),
+                speed,
+                ..default()
+            },
         },
         Music,
         Name::new("Music"),
@@ -22,3 +72,70 @@ pub fn stop_music(mut cmds: Commands, music_q: Query<(&AudioSink, Entity), With<
         cmds.entity(ent).despawn_recursive();
     }
 }
+
+/// Follow volume changes made in the settings menu
+pub fn apply_music_volume(settings: Res<Settings>, music_q: Query<&AudioSink, With<Music>>) {
+    for sink in music_q.iter() {
+        sink.set_volume(settings.music_volume());
+    }
+}
+
+/// Load the tempo sidecar files of the tracks in every level's playlist
+pub fn load_track_infos(
+    assets: Res<AssetServer>,
+    levels: Res<Levels>,
+    mut track_infos: ResMut<TrackInfos>,
+) {
+    for track in levels.0.iter().flat_map(|level| level.playlist.iter()) {
+        if !track_infos.0.contains_key(track) {
+            let handle = assets.load(format!("{}.beat.ron", track));
+            track_infos.0.insert(track.clone(), handle);
+        }
+    }
+}
+
+/// Apply the tempo of the current track to the beat clock once it is loaded (or hot reloaded)
+pub fn sync_track_info(
+    mut clock: ResMut<BeatClock>,
+    mut asset_evr: EventReader<AssetEvent<TrackInfo>>,
+    track_infos: Res<TrackInfos>,
+    current: Res<CurrentTrack>,
+    tracks: Res<Assets<TrackInfo>>,
+) {
+    let Some(current_handle) = track_infos.0.get(&current.0) else { return };
+
+    for ev in asset_evr.iter() {
+        match ev {
+            AssetEvent::Created { handle } | AssetEvent::Modified { handle } => {
+                if handle != current_handle {
+                    continue;
+                }
+
+                if let Some(track) = tracks.get(handle) {
+                    clock.set_track(track);
+                }
+            }
+            AssetEvent::Removed { .. } => (),
+        }
+    }
+}
+
+/// Restart the beat clock whenever the music restarts
+pub fn reset_beat_clock(mut clock: ResMut<BeatClock>) {
+    clock.reset();
+}
+
+/// Advance the beat clock and fire Beat events
+pub fn tick_beat_clock(
+    time: Res<FixedTime>,
+    mut clock: ResMut<BeatClock>,
+    mut beat_evw: EventWriter<Beat>,
+) {
+    clock.elapsed.tick(time.period);
+
+    let position = clock.position();
+    while position >= clock.beats as f32 {
+        beat_evw.send(Beat(clock.beats));
+        clock.beats += 1;
+    }
+}
@@ -0,0 +1,38 @@
+use bevy::prelude::*;
+
+pub mod nav_res;
+mod nav_sys;
+
+use nav_res::*;
+use nav_sys::*;
+
+use crate::{debug::debug_res::EnableDebugMode, game::GameplaySet, AppState};
+
+pub const NAV_CELL_SIZE: f32 = 0.5;
+/// extra space kept between enemies and obstacles
+pub const NAV_CLEARANCE: f32 = 0.2;
+/// height of the box used to probe each cell for obstacles, kept clear of the floor
+pub const NAV_PROBE_HEIGHT: f32 = 0.5;
+pub const NAV_STRAIGHT_COST: u32 = 10;
+pub const NAV_DIAGONAL_COST: u32 = 14;
+
+pub struct NavPlugin;
+
+impl Plugin for NavPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<NavGrid>()
+            .add_systems(OnEnter(AppState::Game), reset_nav_grid)
+            .add_systems(
+                FixedUpdate,
+                (mark_nav_grid_dirty, build_nav_grid, update_flow_field)
+                    .chain()
+                    .in_set(GameplaySet),
+            )
+            .add_systems(
+                Update,
+                draw_nav_grid
+                    .run_if(resource_equals(EnableDebugMode(true)))
+                    .in_set(GameplaySet),
+            );
+    }
+}
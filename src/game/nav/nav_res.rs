@@ -0,0 +1,237 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use bevy::prelude::*;
+
+use super::{NAV_CELL_SIZE, NAV_DIAGONAL_COST, NAV_STRAIGHT_COST};
+
+/// Navigation grid over the arena with a flow field leading to the nearest player
+#[derive(Resource)]
+pub struct NavGrid {
+    /// cells per side
+    pub width: usize,
+    /// half the length of the arena the grid covers
+    pub half_size: f32,
+    pub blocked: Vec<bool>,
+    /// path cost from each cell to the closest target cell, u32::MAX when unreachable
+    pub costs: Vec<u32>,
+    pub targets: Vec<usize>,
+    /// rebuild the grid from the world colliders on the next frame
+    pub dirty: bool,
+}
+
+impl Default for NavGrid {
+    fn default() -> Self {
+        Self {
+            width: 0,
+            half_size: 0.0,
+            blocked: Vec::new(),
+            costs: Vec::new(),
+            targets: Vec::new(),
+            dirty: true,
+        }
+    }
+}
+
+impl NavGrid {
+    pub fn new(blocked: Vec<bool>, width: usize, half_size: f32) -> Self {
+        Self {
+            width,
+            half_size,
+            costs: vec![u32::MAX; blocked.len()],
+            blocked,
+            targets: Vec::new(),
+            dirty: false,
+        }
+    }
+
+    /// Cell index of a world position, None outside of the grid
+    pub fn index(&self, pos: Vec3) -> Option<usize> {
+        if self.width == 0 {
+            return None;
+        }
+
+        let half_map = self.half_size;
+        let x = ((pos.x + half_map) / NAV_CELL_SIZE).floor();
+        let z = ((pos.z + half_map) / NAV_CELL_SIZE).floor();
+
+        let width = self.width as f32;
+        if x < 0.0 || z < 0.0 || x >= width || z >= width {
+            return None;
+        }
+
+        Some(z as usize * self.width + x as usize)
+    }
+
+    /// Whether a world position is on the grid and not blocked by a prop
+    pub fn is_open(&self, pos: Vec3) -> bool {
+        self.index(pos).is_some_and(|idx| !self.blocked[idx])
+    }
+
+    /// World position of the center of a cell, on the floor
+    pub fn center(&self, idx: usize) -> Vec3 {
+        let half_map = self.half_size;
+        let x = (idx % self.width) as f32;
+        let z = (idx / self.width) as f32;
+
+        Vec3::new(
+            x * NAV_CELL_SIZE - half_map + NAV_CELL_SIZE / 2.0,
+            0.0,
+            z * NAV_CELL_SIZE - half_map + NAV_CELL_SIZE / 2.0,
+        )
+    }
+
+    /// Walkable neighbours of a cell and the cost of stepping to them
+    /// Diagonal steps are only allowed when they don't cut the corner of an obstacle
+    pub fn neighbours(&self, idx: usize) -> Vec<(usize, u32)> {
+        let width = self.width as i32;
+        let x = (idx % self.width) as i32;
+        let z = (idx / self.width) as i32;
+
+        let walkable = |x: i32, z: i32| -> bool {
+            x >= 0 && z >= 0 && x < width && z < width && !self.blocked[(z * width + x) as usize]
+        };
+
+        let mut neighbours = Vec::with_capacity(8);
+        for dz in -1..=1 {
+            for dx in -1..=1 {
+                if (dx == 0 && dz == 0) || !walkable(x + dx, z + dz) {
+                    continue;
+                }
+
+                let diagonal = dx != 0 && dz != 0;
+                if diagonal && (!walkable(x + dx, z) || !walkable(x, z + dz)) {
+                    continue;
+                }
+
+                let cost = if diagonal {
+                    NAV_DIAGONAL_COST
+                } else {
+                    NAV_STRAIGHT_COST
+                };
+
+                neighbours.push((((z + dz) * width + x + dx) as usize, cost));
+            }
+        }
+
+        neighbours
+    }
+
+    /// Recompute the path costs of every cell towards the closest target (Dijkstra)
+    pub fn update_flow_field(&mut self, targets: Vec<usize>) {
+        self.costs.fill(u32::MAX);
+
+        let mut heap = BinaryHeap::new();
+        for idx in targets.iter().copied() {
+            self.costs[idx] = 0;
+            heap.push(Reverse((0, idx)));
+        }
+
+        self.targets = targets;
+
+        while let Some(Reverse((cost, idx))) = heap.pop() {
+            if cost > self.costs[idx] {
+                continue;
+            }
+
+            for (next, step_cost) in self.neighbours(idx) {
+                let next_cost = cost + step_cost;
+                if next_cost < self.costs[next] {
+                    self.costs[next] = next_cost;
+                    heap.push(Reverse((next_cost, next)));
+                }
+            }
+        }
+    }
+
+    /// Next cell along the flow field, None once at a target or when there is no path
+    pub fn next_cell(&self, idx: usize) -> Option<usize> {
+        if self.costs[idx] == u32::MAX || self.costs[idx] == 0 {
+            return None;
+        }
+
+        self.neighbours(idx)
+            .into_iter()
+            .map(|(next, _)| next)
+            .filter(|next| self.costs[*next] < self.costs[idx])
+            .min_by_key(|next| self.costs[*next])
+    }
+
+    /// Flat direction to move in to get from `from` to the flow field target at `to`
+    /// Falls back to a straight line when off the grid or without a path
+    pub fn direction(&self, from: Vec3, to: Vec3) -> Vec3 {
+        let mut target = to;
+
+        if let Some(next) = self.index(from).and_then(|idx| self.next_cell(idx)) {
+            target = self.center(next);
+        }
+
+        Vec3::new(target.x - from.x, 0.0, target.z - from.z).normalize_or_zero()
+    }
+
+    /// Flat direction to move in to get from `from` to any point `to` around the obstacles (A*)
+    /// Falls back to a straight line when off the grid, next to `to` or without a path
+    pub fn route(&self, from: Vec3, to: Vec3) -> Vec3 {
+        let straight = Vec3::new(to.x - from.x, 0.0, to.z - from.z).normalize_or_zero();
+        let (Some(start), Some(goal)) = (self.index(from), self.index(to)) else {
+            return straight;
+        };
+
+        // octile distance, never more than the real path cost
+        let heuristic = |idx: usize| {
+            let dx = (idx % self.width).abs_diff(goal % self.width) as u32;
+            let dz = (idx / self.width).abs_diff(goal / self.width) as u32;
+            NAV_STRAIGHT_COST * dx.max(dz) + (NAV_DIAGONAL_COST - NAV_STRAIGHT_COST) * dx.min(dz)
+        };
+
+        let mut costs = vec![u32::MAX; self.blocked.len()];
+        let mut came_from = vec![start; self.blocked.len()];
+        let mut heap = BinaryHeap::new();
+        costs[start] = 0;
+        heap.push(Reverse((heuristic(start), start)));
+
+        while let Some(Reverse((_, idx))) = heap.pop() {
+            if idx == goal {
+                break;
+            }
+
+            for (next, step_cost) in self.neighbours(idx) {
+                let next_cost = costs[idx] + step_cost;
+                if next_cost < costs[next] {
+                    costs[next] = next_cost;
+                    came_from[next] = idx;
+                    heap.push(Reverse((next_cost + heuristic(next), next)));
+                }
+            }
+        }
+
+        if start == goal || costs[goal] == u32::MAX {
+            return straight;
+        }
+
+        // walk back from the goal to the first cell after the start
+        let mut step = goal;
+        while came_from[step] != start {
+            step = came_from[step];
+        }
+
+        if step == goal {
+            return straight;
+        }
+
+        let target = self.center(step);
+        Vec3::new(target.x - from.x, 0.0, target.z - from.z).normalize_or_zero()
+    }
+
+    /// Cell centers along the flow field from `from` to the closest target
+    pub fn path(&self, from: Vec3) -> Vec<Vec3> {
+        let mut path = Vec::new();
+        let mut current = self.index(from);
+
+        while let Some(idx) = current {
+            path.push(self.center(idx));
+            current = self.next_cell(idx);
+        }
+
+        path
+    }
+}
@@ -0,0 +1,103 @@
+use std::f32::consts::FRAC_PI_2;
+
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+
+use crate::game::{
+    enemy::enemy_cmps::Enemy,
+    player::player_cmps::{Downed, Player},
+    world::world_res::Level,
+};
+
+use super::{nav_res::NavGrid, NAV_CELL_SIZE, NAV_CLEARANCE, NAV_PROBE_HEIGHT};
+
+/// Throw away the grid of the last game, it is rebuilt once the new world colliders exist
+pub fn reset_nav_grid(mut grid: ResMut<NavGrid>) {
+    *grid = NavGrid::default();
+}
+
+/// Rebuild the grid whenever rapier picks up new static colliders (world props)
+pub fn mark_nav_grid_dirty(
+    mut grid: ResMut<NavGrid>,
+    new_collider_q: Query<(), (Added<RapierColliderHandle>, Without<RigidBody>)>,
+) {
+    if !new_collider_q.is_empty() {
+        grid.dirty = true;
+    }
+}
+
+/// Probe every cell of the arena against the static rapier colliders
+pub fn build_nav_grid(
+    mut grid: ResMut<NavGrid>,
+    rapier_context: Res<RapierContext>,
+    level: Res<Level>,
+) {
+    if !grid.dirty {
+        return;
+    }
+
+    let width = (level.size / NAV_CELL_SIZE).ceil() as usize;
+    let half_extent = NAV_CELL_SIZE / 2.0 + NAV_CLEARANCE;
+    let probe = Collider::cuboid(half_extent, NAV_PROBE_HEIGHT / 2.0, half_extent);
+
+    let mut nav_grid = NavGrid::new(vec![false; width * width], width, level.half_size());
+    for idx in 0..width * width {
+        let mut pos = nav_grid.center(idx);
+        pos.y = NAV_PROBE_HEIGHT;
+
+        nav_grid.blocked[idx] = rapier_context
+            .intersection_with_shape(pos, Quat::IDENTITY, &probe, QueryFilter::only_fixed())
+            .is_some();
+    }
+
+    *grid = nav_grid;
+}
+
+/// Point the flow field at the living players whenever one of them moves into another cell
+pub fn update_flow_field(
+    mut grid: ResMut<NavGrid>,
+    player_q: Query<&Transform, (With<Player>, Without<Downed>)>,
+) {
+    let mut targets: Vec<usize> = player_q
+        .iter()
+        .filter_map(|trans| grid.index(trans.translation))
+        .collect();
+    targets.sort_unstable();
+    targets.dedup();
+
+    if !targets.is_empty() && targets != grid.targets {
+        grid.update_flow_field(targets);
+    }
+}
+
+/// Debug overlay of the blocked cells and the path of every enemy
+pub fn draw_nav_grid(
+    mut gizmos: Gizmos,
+    grid: Res<NavGrid>,
+    enemy_q: Query<&Transform, With<Enemy>>,
+) {
+    let flat = Quat::from_rotation_x(FRAC_PI_2);
+    let cell = Vec2::splat(NAV_CELL_SIZE);
+
+    for (idx, blocked) in grid.blocked.iter().enumerate() {
+        if *blocked {
+            let pos = grid.center(idx) + Vec3::Y * 0.02;
+            gizmos.rect(pos, flat, cell, Color::RED);
+        }
+    }
+
+    // enemies don't spawn in debug mode, so also show the paths from the arena corners
+    let corner = grid.half_size - NAV_CELL_SIZE;
+    let corners = [
+        Vec3::new(corner, 0.0, corner),
+        Vec3::new(-corner, 0.0, corner),
+        Vec3::new(corner, 0.0, -corner),
+        Vec3::new(-corner, 0.0, -corner),
+    ];
+
+    let enemies = enemy_q.iter().map(|trans| trans.translation);
+    for start in corners.into_iter().chain(enemies) {
+        let path = grid.path(start);
+        gizmos.linestrip(path.into_iter().map(|p| p + Vec3::Y * 0.05), Color::CYAN);
+    }
+}
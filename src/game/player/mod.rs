@@ -4,35 +4,50 @@ pub mod player_cmps;
 pub mod player_res;
 pub mod player_sys;
 
-use crate::{gamepad::gamepad_rcs::MyGamepad, AppState};
+use crate::{
+    game::{config::config_res::GameConfig, stats::StatsSet, GameplaySet, RunSetupSet},
+    AppState,
+};
 use player_res::*;
 use player_sys::*;
 
-pub const PLAYER_SPEED: f32 = 2.5;
-pub const PLAYER_HP: f32 = 100.0;
-pub const STAMINA: f32 = 100.0;
-pub const SPRINT_SPEED: f32 = 2.0;
 pub const PLAYER_SIZE: f32 = 0.5;
-pub const STAMINA_REGEN_TIME: f32 = 1.5;
-pub const STAMINA_REGEN_AMOUNT: f32 = 0.025;
+/// distance between players spawned next to each other in co-op
+pub const PLAYER_SPAWN_SPACING: f32 = 1.5;
+
+/// Moving the players, order anything that needs where they stood this tick before it
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PlayerMoveSet;
 
 pub struct PlayerPlugin;
 
 impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<KillCount>()
-            .add_systems(OnEnter(AppState::Game), (spawn_player, reset_killcount))
+            .init_resource::<RhythmAccuracy>()
+            .init_resource::<Party>()
+            .init_resource::<RunStats>()
+            .add_systems(
+                OnEnter(AppState::Game),
+                (
+                    spawn_players.after(RunSetupSet),
+                    reset_killcount,
+                    reset_rhythm_accuracy,
+                    reset_run_stats,
+                ),
+            )
             .add_systems(
-                Update,
+                FixedUpdate,
                 (
-                    decrease_hp,
-                    keyboard_movement,
-                    gamepad_movement.run_if(resource_exists::<MyGamepad>()),
+                    decrease_hp.after(StatsSet),
+                    expire_shields,
+                    player_movement.after(StatsSet).in_set(PlayerMoveSet),
                     update_stamina,
                     increase_killcount,
-                    player_death,
+                    (down_players, revive_players, player_death).chain(),
+                    apply_player_config.run_if(resource_exists_and_changed::<GameConfig>()),
                 )
-                    .run_if(in_state(AppState::Game)),
+                    .in_set(GameplaySet),
             );
     }
 }
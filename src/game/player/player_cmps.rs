@@ -2,50 +2,85 @@ use std::time::Duration;
 
 use bevy::prelude::*;
 use bevy_rapier3d::prelude::*;
+use bevy_third_person_camera::ThirdPersonCamera;
+use serde::{Deserialize, Serialize};
 
-use crate::game::game_cmps::{Damage, Game, Hp, Speed};
+use crate::actions::actions_res::{InputActions, InputSource};
+use crate::game::{
+    config::config_res::GameConfig,
+    effects::effects_cmps::StatusEffects,
+    game_cmps::{Damage, Game, Hp, Speed},
+    stats::stats_cmps::{Attribute, DamageTaken, FireRate, Modifiers},
+    weapons::weapons_cmps::Arsenal,
+};
 
-use super::{PLAYER_HP, PLAYER_SIZE, PLAYER_SPEED, STAMINA, STAMINA_REGEN_TIME};
+use super::PLAYER_SIZE;
 
 #[derive(Bundle)]
 pub struct PlayerBundle {
+    pub arsenal: Arsenal,
+    pub cash: Cash,
     pub collider: Collider,
     pub controller: KinematicCharacterController,
+    pub controlled_by: ControlledBy,
     pub damage: Damage,
+    pub damage_taken: DamageTaken,
+    pub fire_rate: FireRate,
     pub friction: Friction,
     pub game: Game,
     pub hp: Hp,
+    pub input: PlayerInput,
     pub is_sprinting: IsSprinting,
     pub is_shooting: IsShooting,
+    pub kills: Kills,
     pub locked_axes: LockedAxes,
+    pub modifiers: Modifiers,
     pub name: Name,
     pub player: Player,
+    pub player_id: PlayerId,
     pub rigid_body: RigidBody,
     pub stamina: Stamina,
     pub speed: Speed,
+    pub status_effects: StatusEffects,
 }
 
-impl Default for PlayerBundle {
-    fn default() -> Self {
+impl PlayerBundle {
+    pub fn new(config: &GameConfig, id: PlayerId, source: InputSource) -> Self {
+        let player = &config.player;
+
         Self {
+            arsenal: Arsenal::new(config),
+            cash: Cash(0),
             collider: Collider::capsule(
                 Vec3::new(0.0, -0.25, 0.0),
                 Vec3::new(0.0, 0.25, 0.0),
                 PLAYER_SIZE / 2.0,
             ),
             controller: KinematicCharacterController { ..default() },
-            damage: Damage::new(25.0),
+            controlled_by: ControlledBy(source),
+            damage: Damage::new(player.damage),
+            damage_taken: DamageTaken::default(),
+            fire_rate: FireRate::default(),
             friction: Friction::coefficient(0.0),
             game: Game,
-            hp: Hp::new(PLAYER_HP),
+            hp: Hp::new(player.hp),
+            input: PlayerInput::default(),
             is_sprinting: IsSprinting(false),
             is_shooting: IsShooting(false),
+            kills: Kills(0),
             locked_axes: LockedAxes::ROTATION_LOCKED_X | LockedAxes::ROTATION_LOCKED_Z,
-            name: Name::new("Player"),
+            modifiers: Modifiers::default(),
+            name: Name::new(format!("Player {}", id.0 + 1)),
             player: Player,
+            player_id: id,
             rigid_body: RigidBody::Dynamic,
-            stamina: Stamina::new(STAMINA),
-            speed: Speed(PLAYER_SPEED),
+            stamina: Stamina::new(
+                player.stamina,
+                player.stamina_regen_amount,
+                player.stamina_regen_time,
+            ),
+            speed: Speed::new(player.speed),
+            status_effects: StatusEffects::default(),
         }
     }
 }
@@ -53,18 +88,76 @@ impl Default for PlayerBundle {
 #[derive(Component)]
 pub struct Player;
 
+/// Local player number starting at 0, shared by the player's camera and HUD
+#[derive(Component, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct PlayerId(pub usize);
+
+/// Device the player's actions are read from
+#[derive(Component)]
+pub struct ControlledBy(pub InputSource);
+
+/// What the player does this gameplay tick, the only input the simulation reads
+/// Sampled from their device at the start of every tick, or read back from a replay
+#[derive(Component, Serialize, Deserialize, Default, Clone, Copy, PartialEq)]
+pub struct PlayerInput {
+    pub actions: InputActions,
+    /// rotation of the player's camera, movement & shots are relative to it
+    pub aim: Quat,
+}
+
+impl PlayerInput {
+    pub fn aim(&self) -> Transform {
+        Transform::from_rotation(self.aim)
+    }
+}
+
+/// Enemies killed by this player
+#[derive(Component)]
+pub struct Kills(pub u32);
+
+/// Currency picked up from enemy drops this run
+#[derive(Component)]
+pub struct Cash(pub u32);
+
+/// Damage soaked up before it reaches hp, lasts as long as the Shield effect
+#[derive(Component)]
+pub struct Shield(pub f32);
+
+/// Out of hp, waiting for a teammate to stand next to them long enough to revive them
+#[derive(Component)]
+pub struct Downed {
+    pub revive: Timer,
+}
+
+/// Players that are still up, downed players can't move, shoot, pick up or be attacked
+pub type LivingPlayer = (With<Player>, Without<Downed>);
+
+/// Cameras following the players, disjoint from player queries on Transform
+pub type PlayerCam = (With<ThirdPersonCamera>, Without<Player>);
+
+impl Downed {
+    pub fn new(revive_time: f32) -> Self {
+        Self {
+            revive: Timer::from_seconds(revive_time, TimerMode::Once),
+        }
+    }
+}
+
 #[derive(Component)]
 pub struct Stamina {
-    pub max: f32,
+    pub max: Attribute,
+    /// stamina regenerated per tick
+    pub regen: Attribute,
     pub regen_time: Timer,
     pub value: f32,
 }
 
 impl Stamina {
-    pub fn new(max: f32) -> Self {
+    pub fn new(max: f32, regen: f32, regen_time: f32) -> Self {
         Self {
-            max,
-            regen_time: Timer::new(Duration::from_secs_f32(STAMINA_REGEN_TIME), TimerMode::Once),
+            max: Attribute::new(max),
+            regen: Attribute::new(regen),
+            regen_time: Timer::new(Duration::from_secs_f32(regen_time), TimerMode::Once),
             value: max,
         }
     }
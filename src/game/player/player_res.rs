@@ -1,5 +1,7 @@
 use bevy::prelude::*;
 
+use crate::actions::actions_res::InputSource;
+
 #[derive(Resource)]
 pub struct KillCount(pub u32);
 
@@ -8,3 +10,37 @@ impl Default for KillCount {
         KillCount(0)
     }
 }
+
+/// Shots fired this run and how many of them landed on the beat
+#[derive(Resource, Default)]
+pub struct RhythmAccuracy {
+    pub shots: u32,
+    pub on_beat: u32,
+}
+
+impl RhythmAccuracy {
+    pub fn percent(&self) -> f32 {
+        if self.shots == 0 {
+            return 0.0;
+        }
+
+        self.on_beat as f32 / self.shots as f32 * 100.0
+    }
+}
+
+/// Input source of every local player, a player is spawned for each of them
+#[derive(Resource)]
+pub struct Party(pub Vec<InputSource>);
+
+impl Default for Party {
+    fn default() -> Self {
+        Party(vec![InputSource::Any])
+    }
+}
+
+/// Damage taken and powerups collected this run
+#[derive(Resource, Default)]
+pub struct RunStats {
+    pub damage_taken: f32,
+    pub powerups: u32,
+}
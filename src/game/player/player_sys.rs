@@ -1,76 +1,83 @@
-use super::player_res::KillCount;
+use std::time::Duration;
+
+use super::player_res::{KillCount, Party, RhythmAccuracy, RunStats};
 use super::{player_cmps::*, *};
+use crate::actions::actions_res::Action;
+use crate::game::bot::{bot_cmps::BotController, bot_res::BotPlayers};
+use crate::game::config::config_res::GameConfig;
+use crate::game::effects::effects_cmps::{EffectKind, StatusEffects};
 use crate::game::enemy::enemy_evs::{EnemyDeathEv, HitPlayerEv};
-use crate::game::game_cmps::{Hp, Speed};
+use crate::game::game_cmps::{Damage, Hp, Speed};
 use crate::game::game_evs::GameOver;
-use crate::gamepad::gamepad_rcs::MyGamepad;
-use bevy_third_person_camera::{ThirdPersonCamera, ThirdPersonCameraTarget};
-
-pub fn spawn_player(mut cmds: Commands, assets: Res<AssetServer>) {
-    cmds.spawn((
-        SceneBundle {
-            scene: assets.load("models/Player.gltf#Scene0"),
-            transform: Transform {
-                translation: Vec3::new(0.0, 0.5, 0.0),
-                ..default()
-            },
-            ..default()
-        },
-        ThirdPersonCameraTarget,
-        PlayerBundle::default(),
-    ));
-}
+use crate::game::stats::stats_cmps::DamageTaken;
+use bevy_third_person_camera::ThirdPersonCameraTarget;
+
+type MovingPlayer<'a> = (
+    &'a mut Transform,
+    &'a Speed,
+    &'a mut IsSprinting,
+    &'a Stamina,
+    &'a IsShooting,
+    &'a PlayerInput,
+);
 
-pub fn keyboard_movement(
-    time: Res<Time>,
-    keys: Res<Input<KeyCode>>,
-    mut player_q: Query<
-        (
-            &mut Transform,
-            &Speed,
-            &mut IsSprinting,
-            &Stamina,
-            &IsShooting,
-        ),
-        With<Player>,
-    >,
-    cam_q: Query<&Transform, (With<ThirdPersonCamera>, Without<Player>)>,
+/// Spawn a player for every input source in the party, side by side
+pub fn spawn_players(
+    mut cmds: Commands,
+    assets: Res<AssetServer>,
+    config: Res<GameConfig>,
+    party: Res<Party>,
+    bots: Option<Res<BotPlayers>>,
 ) {
-    for (mut player_transform, speed, mut is_sprinting, stamina, is_shooting) in player_q.iter_mut()
-    {
-        let Ok(cam) = cam_q.get_single() else { return };
+    let players = party.0.len();
 
-        let mut direction = Vec3::ZERO;
+    for (idx, source) in party.0.iter().enumerate() {
+        let x = (idx as f32 - (players - 1) as f32 / 2.0) * PLAYER_SPAWN_SPACING;
 
-        // forward
-        if keys.pressed(KeyCode::W) {
-            direction += cam.forward();
-        }
+        let mut player = cmds.spawn((
+            SceneBundle {
+                scene: assets.load("models/Player.gltf#Scene0"),
+                transform: Transform {
+                    translation: Vec3::new(x, 0.5, 0.0),
+                    ..default()
+                },
+                ..default()
+            },
+            ThirdPersonCameraTarget,
+            PlayerBundle::new(&config, PlayerId(idx), *source),
+        ));
 
-        // back
-        if keys.pressed(KeyCode::S) {
-            direction += cam.back();
+        if bots.is_some() {
+            player.insert(BotController::default());
         }
+    }
+}
 
-        // left
-        if keys.pressed(KeyCode::A) {
-            direction += cam.left();
-        }
+/// Move relative to the player's camera from their move actions, sprinting while stamina lasts
+pub fn player_movement(
+    time: Res<FixedTime>,
+    config: Res<GameConfig>,
+    mut player_q: Query<MovingPlayer, LivingPlayer>,
+) {
+    for (mut player_transform, speed, mut is_sprinting, stamina, is_shooting, input) in
+        player_q.iter_mut()
+    {
+        let actions = &input.actions;
+        let cam = input.aim();
 
-        // right
-        if keys.pressed(KeyCode::D) {
-            direction += cam.right();
-        }
+        let input = actions.movement();
+        let mut direction = cam.forward() * input.y + cam.right() * input.x;
 
         // sprint
         let mut sprint = 1.0;
-        if keys.pressed(KeyCode::ShiftLeft) && stamina.value > 0.0 {
-            sprint = SPRINT_SPEED;
+        if actions.pressed(Action::Sprint) && stamina.value > 0.0 {
+            sprint = config.player.sprint_speed;
             is_sprinting.0 = true;
         }
 
         direction.y = 0.0;
-        let movement = direction.normalize_or_zero() * sprint * speed.0 * time.delta_seconds();
+        let movement =
+            direction.normalize_or_zero() * sprint * speed.0.value * time.period.as_secs_f32();
         player_transform.translation += movement;
 
         // rotate player to face direction he is currently moving
@@ -80,77 +87,9 @@ pub fn keyboard_movement(
     }
 }
 
-pub fn gamepad_movement(
-    time: Res<Time>,
-    axis: Res<Axis<GamepadAxis>>,
-    btns: Res<Input<GamepadButton>>,
-    mut player_q: Query<(&mut Transform, &Speed, &mut IsSprinting, &Stamina), With<Player>>,
-    cam_q: Query<&Transform, (With<ThirdPersonCamera>, Without<Player>)>,
-    my_gamepad: Option<Res<MyGamepad>>,
-) {
-    // return id of gamepad if one is connected
-    let gamepad = if let Some(gp) = my_gamepad {
-        gp.gamepad
-    } else {
-        return;
-    };
-
-    // get X & Y axis of left joystick
-    let x_axis = GamepadAxis {
-        axis_type: GamepadAxisType::LeftStickX,
-        gamepad,
-    };
-    let y_axis = GamepadAxis {
-        axis_type: GamepadAxisType::LeftStickY,
-        gamepad,
-    };
-
-    let mut left_joystick = Vec2::ZERO;
-    if let (Some(x), Some(y)) = (axis.get(x_axis), axis.get(y_axis)) {
-        left_joystick = Vec2::new(x, y);
-    }
-
-    for (mut player_trans, speed, mut sprinting, stamina) in player_q.iter_mut() {
-        let cam = match cam_q.get_single() {
-            Ok(c) => c,
-            Err(e) => Err(format!("Error retrieving camera: {}", e)).unwrap(),
-        };
-
-        let mut direction = Vec3::ZERO;
-
-        if left_joystick.length() > 0.5 {
-            // Get the direction of the joystick relative to the camera
-            let forward = cam.forward().normalize();
-            let right = cam.right().normalize();
-            let mut joystick_direction = forward * left_joystick.y + right * left_joystick.x;
-            joystick_direction.y = 0.0;
-            joystick_direction = joystick_direction.normalize();
-
-            // Move the player in the joystick direction
-            direction += joystick_direction;
-        }
-
-        // sprint
-        let mut sprint = 1.0;
-        let left_thumb = GamepadButton::new(gamepad, GamepadButtonType::LeftTrigger);
-        if btns.pressed(left_thumb) && stamina.value > 0.0 {
-            sprint = SPRINT_SPEED;
-            sprinting.0 = true;
-        }
-
-        direction.y = 0.0;
-        player_trans.translation += speed.0 * sprint * direction * time.delta_seconds();
-
-        // rotate player to face direction he is currently moving
-        if direction.length_squared() > 0.0 {
-            player_trans.look_to(direction, Vec3::Y);
-        }
-    }
-}
-
 pub fn update_stamina(
     mut player_q: Query<(&mut Stamina, &mut IsSprinting), With<Player>>,
-    time: Res<Time>,
+    time: Res<FixedTime>,
 ) {
     for (mut stamina, mut sprinting) in player_q.iter_mut() {
         // if sprinting & stamina is greater than zero, drain stamina & reset regen timer
@@ -159,49 +98,164 @@ pub fn update_stamina(
             stamina.regen_time.reset();
 
         // if regen timer finished & stamina is less than max, regenerate stamina
-        } else if stamina.regen_time.just_finished() && stamina.value < stamina.max {
-            stamina.value += STAMINA_REGEN_AMOUNT;
+        } else if stamina.regen_time.just_finished() && stamina.value < stamina.max.value {
+            stamina.value += stamina.regen.value;
 
         // if stamina is less than the max, tick the regen timer
-        } else if stamina.value < stamina.max {
-            stamina.regen_time.tick(time.delta());
+        } else if stamina.value < stamina.max.value {
+            stamina.regen_time.tick(time.period);
         }
 
         sprinting.0 = false;
     }
 }
 
-pub fn player_death(player_q: Query<&Hp, With<Player>>, mut game_over_evw: EventWriter<GameOver>) {
-    if let Ok(hp) = player_q.get_single() {
+/// Pick up player stat changes from a reloaded gameplay config
+/// Only the base values change, modifiers stay on top of them
+pub fn apply_player_config(
+    config: Res<GameConfig>,
+    mut player_q: Query<(&mut Speed, &mut Hp, &mut Stamina, &mut Damage), With<Player>>,
+) {
+    let player = &config.player;
+
+    for (mut speed, mut hp, mut stamina, mut dmg) in player_q.iter_mut() {
+        speed.0.base = player.speed;
+        hp.max.base = player.hp;
+        stamina.max.base = player.stamina;
+        stamina.regen.base = player.stamina_regen_amount;
+        stamina
+            .regen_time
+            .set_duration(Duration::from_secs_f32(player.stamina_regen_time));
+        dmg.0.base = player.damage;
+    }
+}
+
+/// Players that run out of hp go down instead of dying
+pub fn down_players(
+    mut cmds: Commands,
+    config: Res<GameConfig>,
+    player_q: Query<(Entity, &Hp), LivingPlayer>,
+) {
+    for (ent, hp) in player_q.iter() {
         if hp.value <= 0.0 {
-            game_over_evw.send(GameOver);
+            cmds.entity(ent)
+                .insert(Downed::new(config.player.revive_time));
+        }
+    }
+}
+
+/// A downed player is revived once a teammate has stood next to them for the revive time
+pub fn revive_players(
+    mut cmds: Commands,
+    time: Res<FixedTime>,
+    config: Res<GameConfig>,
+    mut downed_q: Query<(Entity, &Transform, &mut Hp, &mut Downed), With<Player>>,
+    helper_q: Query<&Transform, LivingPlayer>,
+) {
+    for (ent, trans, mut hp, mut downed) in downed_q.iter_mut() {
+        let helped = helper_q.iter().any(|helper| {
+            helper.translation.distance(trans.translation) < config.player.revive_radius
+        });
+
+        // walking away loses the progress
+        if !helped {
+            downed.revive.reset();
+            continue;
+        }
+
+        downed.revive.tick(time.period);
+
+        if downed.revive.finished() {
+            hp.value = hp.max.value * config.player.revive_hp;
+            cmds.entity(ent).remove::<Downed>();
         }
     }
 }
 
-pub fn decrease_hp(mut evr: EventReader<HitPlayerEv>, mut player_q: Query<&mut Hp, With<Player>>) {
+/// The run is over once every player is down
+pub fn player_death(
+    player_q: Query<(&Hp, Option<&Downed>), With<Player>>,
+    mut game_over_evw: EventWriter<GameOver>,
+) {
+    let all_down = player_q
+        .iter()
+        .all(|(hp, downed)| downed.is_some() || hp.value <= 0.0);
+
+    if !player_q.is_empty() && all_down {
+        game_over_evw.send(GameOver);
+    }
+}
+
+pub fn decrease_hp(
+    mut evr: EventReader<HitPlayerEv>,
+    mut stats: ResMut<RunStats>,
+    mut player_q: Query<(&mut Hp, &DamageTaken, Option<&mut Shield>), LivingPlayer>,
+) {
     for ev in evr.iter() {
-        if let Ok(mut hp) = player_q.get_single_mut() {
+        if let Ok((mut hp, damage_taken, shield)) = player_q.get_mut(ev.ent) {
             // only decrease hp if hp is > 0
             if hp.value > 0.0 {
-                hp.value -= ev.0;
+                let mut dmg = ev.dmg * damage_taken.0.value;
+
+                // the shield soaks up what it can, expire_shields drops it once it's empty
+                if let Some(mut shield) = shield {
+                    let absorbed = dmg.min(shield.0);
+                    shield.0 -= absorbed;
+                    dmg -= absorbed;
+                }
+
+                let before = hp.value;
+                hp.value -= dmg;
 
                 if hp.value < 0.0 {
                     hp.value = 0.0;
                 }
+
+                stats.damage_taken += before - hp.value;
             }
         }
     }
 }
+
+/// Drop shields that are used up or whose effect ran out
+pub fn expire_shields(
+    mut cmds: Commands,
+    mut player_q: Query<(Entity, &Shield, &mut StatusEffects), With<Player>>,
+) {
+    for (ent, shield, mut effects) in player_q.iter_mut() {
+        if shield.0 <= 0.0 {
+            effects.remove(EffectKind::Shield);
+        }
+
+        if !effects.has(EffectKind::Shield) {
+            cmds.entity(ent).remove::<Shield>();
+        }
+    }
+}
+
 pub fn reset_killcount(mut kills: ResMut<KillCount>) {
     kills.0 = 0;
 }
 
+pub fn reset_rhythm_accuracy(mut accuracy: ResMut<RhythmAccuracy>) {
+    *accuracy = RhythmAccuracy::default();
+}
+
+pub fn reset_run_stats(mut stats: ResMut<RunStats>) {
+    *stats = RunStats::default();
+}
+
+/// Count every kill for the run and for the player who landed it
 pub fn increase_killcount(
     mut kills: ResMut<KillCount>,
     mut enemy_death_evr: EventReader<EnemyDeathEv>,
+    mut player_q: Query<&mut Kills, With<Player>>,
 ) {
-    for _ev in enemy_death_evr.iter() {
+    for ev in enemy_death_evr.iter() {
         kills.0 += 1;
+
+        if let Ok(mut player_kills) = player_q.get_mut(ev.killer) {
+            player_kills.0 += 1;
+        }
     }
 }
@@ -1,39 +1,58 @@
 use bevy::prelude::*;
 
 pub mod powerups_cmps;
+pub mod powerups_evs;
 pub mod powerups_res;
 mod powerups_sys;
 
-use powerups_res::*;
+use powerups_evs::*;
 use powerups_sys::*;
 
-use crate::AppState;
+use crate::{
+    game::{config::config_res::GameConfig, AddTickEvent, GameplaySet},
+    AppState,
+};
 
-pub const POWERUP_SPAWN_TIME: f32 = 8.0;
-pub const DMG_BOOST_DURATION: u64 = 15;
-pub const DMG_BOOST: f32 = 25.0;
-pub const HP_BOOST: f32 = 20.0;
 pub const POWERUP_DISPLAY_DURATION: f32 = 2.0;
+/// height above the floor powerups float at
+pub const POWERUP_HEIGHT: f32 = 0.3;
+pub const POWERUP_BOB_HEIGHT: f32 = 0.05;
+pub const POWERUP_BOB_SPEED: f32 = 3.0;
+/// radians per second
+pub const POWERUP_SPIN_SPEED: f32 = 2.0;
+/// seconds a blinking powerup stays on or off
+pub const POWERUP_BLINK_INTERVAL: f32 = 0.2;
+pub const POWERUP_LIGHT_INTENSITY: f32 = 40.0;
+pub const POWERUP_LIGHT_RANGE: f32 = 1.5;
+/// random points tried before giving up on a spawn because they're all inside props
+pub const POWERUP_SPAWN_ATTEMPTS: u32 = 10;
 
 pub struct PowerUpsPlugin;
 
 impl Plugin for PowerUpsPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<PowerUpSpawnTime>()
-            .init_resource::<DamageBoostDuration>()
+        app.add_tick_event::<PowerUpCollected>()
+            .add_systems(
+                FixedUpdate,
+                (
+                    (tick_powerup_spawn_time, spawn_powerups).chain(),
+                    despawn_expired_powerups,
+                    (pull_powerups, collect_powerups, drop_loot).chain(),
+                    count_powerups,
+                    apply_powerup_config.run_if(resource_exists_and_changed::<GameConfig>()),
+                )
+                    .in_set(GameplaySet),
+            )
             .add_systems(
                 Update,
                 (
-                    spawn_powerups,
-                    collect_stamina_powerup,
-                    collect_dmg_powerup,
-                    collect_hp_powerup,
-                    tick_dmg_duration_timer,
+                    show_powerup_text,
                     despawn_powerup_display,
-                    update_dmg_powerup_duration_display,
+                    animate_powerups,
+                    blink_powerups,
                 )
-                    .run_if(in_state(AppState::Game)),
+                    .in_set(GameplaySet),
             )
-            .add_systems(OnEnter(AppState::Game), spawn_dmg_powerup_duration_display);
+            .add_systems(OnEnter(AppState::Game), reset_powerup_timers);
     }
 }
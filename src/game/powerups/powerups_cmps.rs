@@ -1,18 +1,87 @@
 use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::game::effects::effects_cmps::EffectKind;
 
 use super::POWERUP_DISPLAY_DURATION;
 
 #[derive(Component)]
-pub struct StaminaPowerUp;
+pub struct PowerUp(pub PowerUpKind);
 
+/// Time left before an uncollected powerup despawns
 #[derive(Component)]
-pub struct HpPowerUp;
+pub struct PowerUpLifetime(pub Timer);
 
-#[derive(Component)]
-pub struct DamagePowerUp;
+impl PowerUpLifetime {
+    pub fn new(seconds: f32) -> Self {
+        PowerUpLifetime(Timer::from_seconds(seconds, TimerMode::Once))
+    }
+}
 
+/// Mesh of a powerup, spins and bobs
 #[derive(Component)]
-pub struct DamagePowerUpDurationDisplay;
+pub struct PowerUpModel;
+
+/// Every powerup has its stats in the gameplay config
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PowerUpKind {
+    Stamina,
+    Health,
+    Damage,
+    Speed,
+    Shield,
+    Multishot,
+    Freeze,
+    Nuke,
+    Ammo,
+    Cash,
+}
+
+impl PowerUpKind {
+    pub const ALL: [PowerUpKind; 10] = [
+        PowerUpKind::Stamina,
+        PowerUpKind::Health,
+        PowerUpKind::Damage,
+        PowerUpKind::Speed,
+        PowerUpKind::Shield,
+        PowerUpKind::Multishot,
+        PowerUpKind::Freeze,
+        PowerUpKind::Nuke,
+        PowerUpKind::Ammo,
+        PowerUpKind::Cash,
+    ];
+}
+
+#[derive(Deserialize, Clone)]
+pub struct PowerUpStats {
+    /// shown on the HUD of the player who picked it up
+    pub text: String,
+    pub color: Color,
+    /// chance to spawn on the timer relative to the other powerups, 0 only drops as enemy loot
+    pub weight: u32,
+    pub pickup: Pickup,
+}
+
+/// What picking a powerup up does
+#[derive(Deserialize, Clone, Copy)]
+pub enum Pickup {
+    /// refill the collector's stamina
+    Stamina,
+    /// heal the collector up to their max hp
+    Heal { hp: f32 },
+    /// put an effect on the collector
+    Effect(EffectKind),
+    /// soak up `hp` damage for the collector while their Shield effect lasts
+    Shield { hp: f32 },
+    /// put an effect on every enemy
+    EnemyEffect(EffectKind),
+    /// kill every enemy, the kills go to the collector
+    Nuke,
+    /// add magazines to the reserve of every weapon the collector carries
+    Ammo { magazines: u32 },
+    /// add to the collector's cash
+    Cash { amount: u32 },
+}
 
 #[derive(Component)]
 pub struct PowerUpDisplay {
@@ -0,0 +1,10 @@
+use bevy::prelude::*;
+
+use super::powerups_cmps::PowerUpKind;
+
+#[derive(Event)]
+pub struct PowerUpCollected {
+    /// player that picked it up
+    pub player: Entity,
+    pub kind: PowerUpKind,
+}
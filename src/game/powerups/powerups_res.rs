@@ -1,28 +1,12 @@
-use std::time::Duration;
-
 use bevy::prelude::*;
 
-use super::*;
+use crate::game::config::config_res::PowerUpConfig;
 
 #[derive(Resource)]
 pub struct PowerUpSpawnTime(pub Timer);
 
-impl Default for PowerUpSpawnTime {
-    fn default() -> Self {
-        PowerUpSpawnTime(Timer::from_seconds(
-            POWERUP_SPAWN_TIME,
-            TimerMode::Repeating,
-        ))
-    }
-}
-
-#[derive(Resource)]
-pub struct DamageBoostDuration(pub Timer);
-
-impl Default for DamageBoostDuration {
-    fn default() -> Self {
-        let mut duration = Timer::new(Duration::from_secs(DMG_BOOST_DURATION), TimerMode::Once);
-        duration.pause();
-        DamageBoostDuration(duration)
+impl PowerUpSpawnTime {
+    pub fn new(config: &PowerUpConfig) -> Self {
+        PowerUpSpawnTime(Timer::from_seconds(config.spawn_time, TimerMode::Repeating))
     }
 }
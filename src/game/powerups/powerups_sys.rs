@@ -1,241 +1,389 @@
-use bevy::prelude::*;
+use std::time::Duration;
+
+use bevy::{ecs::system::SystemParam, prelude::*};
 use rand::Rng;
 
 use crate::game::{
-    game_cmps::{Damage, Game, Hp},
+    config::config_res::GameConfig,
+    effects::effects_cmps::{EffectKind, StatusEffects},
+    enemy::{
+        enemy_cmps::{Enemy, EnemyKind},
+        enemy_evs::EnemyDeathEv,
+    },
+    game_cmps::{Game, Hp},
+    game_res::{GameRng, RngStream},
+    hud::hud_cmps::PlayerHud,
+    nav::nav_res::NavGrid,
     player::{
-        player_cmps::{Player, Stamina},
+        player_cmps::{Cash, LivingPlayer, Player, PlayerId, Shield, Stamina},
+        player_res::RunStats,
         PLAYER_SIZE,
     },
-    world::MAP_SIZE,
+    waves::waves_res::WaveDirector,
+    weapons::weapons_cmps::Arsenal,
+    world::world_res::Level,
 };
 
 use super::{
-    powerups_cmps::{
-        DamagePowerUp, DamagePowerUpDurationDisplay, HpPowerUp, PowerUpDisplay, StaminaPowerUp,
-    },
-    powerups_res::{DamageBoostDuration, PowerUpSpawnTime},
-    DMG_BOOST, HP_BOOST,
+    powerups_cmps::{Pickup, PowerUp, PowerUpDisplay, PowerUpKind, PowerUpLifetime, PowerUpModel},
+    powerups_evs::PowerUpCollected,
+    powerups_res::PowerUpSpawnTime,
+    POWERUP_BLINK_INTERVAL, POWERUP_BOB_HEIGHT, POWERUP_BOB_SPEED, POWERUP_HEIGHT,
+    POWERUP_LIGHT_INTENSITY, POWERUP_LIGHT_RANGE, POWERUP_SPAWN_ATTEMPTS, POWERUP_SPIN_SPEED,
 };
 
-pub fn spawn_powerups(
-    mut cmds: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
-    mut spawn_timer: ResMut<PowerUpSpawnTime>,
-    time: Res<Time>,
-) {
-    spawn_timer.0.tick(time.delta());
-    let mut rng = rand::thread_rng();
+/// Players that can pick up powerups, disjoint from enemy queries on Transform
+type CollectingPlayer = (LivingPlayer, Without<Enemy>);
 
-    let map_bounds = MAP_SIZE / 2.0;
-    let x = rng.gen_range(-map_bounds..=map_bounds);
-    let z = rng.gen_range(-map_bounds..=map_bounds);
+/// Enemies hit by freeze and nuke powerups
+type AffectedEnemy = (With<Enemy>, Without<Player>);
+
+type AffectedEnemyQuery<'a> = (
+    Entity,
+    &'a mut Hp,
+    &'a Transform,
+    &'a EnemyKind,
+    &'a mut StatusEffects,
+);
+
+type CollectingPlayerQuery<'a> = (
+    Entity,
+    &'a Transform,
+    &'a mut Hp,
+    &'a mut Stamina,
+    &'a mut StatusEffects,
+    &'a mut Arsenal,
+    &'a mut Cash,
+);
+
+/// Start every game with fresh powerup timers
+pub fn reset_powerup_timers(mut cmds: Commands, config: Res<GameConfig>) {
+    cmds.insert_resource(PowerUpSpawnTime::new(&config.powerups));
+}
+
+/// Pick up powerup timing changes from a reloaded gameplay config
+pub fn apply_powerup_config(config: Res<GameConfig>, mut spawn_timer: ResMut<PowerUpSpawnTime>) {
+    spawn_timer
+        .0
+        .set_duration(Duration::from_secs_f32(config.powerups.spawn_time));
+}
 
-    let mut powerup = |color: Color| -> PbrBundle {
-        PbrBundle {
-            material: materials.add(StandardMaterial {
-                emissive: color.into(),
+pub fn tick_powerup_spawn_time(mut spawn_timer: ResMut<PowerUpSpawnTime>, time: Res<FixedTime>) {
+    spawn_timer.0.tick(time.period);
+}
+
+/// Everything needed to put a powerup on the floor
+#[derive(SystemParam)]
+pub struct PowerUpSpawner<'w, 's> {
+    cmds: Commands<'w, 's>,
+    meshes: ResMut<'w, Assets<Mesh>>,
+    materials: ResMut<'w, Assets<StandardMaterial>>,
+    config: Res<'w, GameConfig>,
+}
+
+impl PowerUpSpawner<'_, '_> {
+    /// Spawn a powerup on the floor at pos, its model & light are children so they can bob and blink
+    pub fn spawn(&mut self, kind: PowerUpKind, pos: Vec3) {
+        let color = self.config.powerup(kind).color;
+        let lifetime = self.config.powerups.lifetime;
+
+        let model = (
+            PbrBundle {
+                material: self.materials.add(StandardMaterial {
+                    emissive: color,
+                    ..default()
+                }),
+                mesh: self.meshes.add(Mesh::from(shape::Cylinder {
+                    height: 0.2,
+                    radius: 0.1,
+                    ..default()
+                })),
                 ..default()
-            }),
-            mesh: meshes.add(Mesh::from(shape::Cylinder {
-                height: 0.2,
-                radius: 0.1,
+            },
+            PowerUpModel,
+            Name::new("PowerUp Model"),
+        );
+
+        let light = (
+            PointLightBundle {
+                point_light: PointLight {
+                    color,
+                    intensity: POWERUP_LIGHT_INTENSITY,
+                    range: POWERUP_LIGHT_RANGE,
+                    ..default()
+                },
                 ..default()
-            })),
-            transform: Transform::from_xyz(x, 0.3, z),
-            ..default()
+            },
+            Name::new("PowerUp Light"),
+        );
+
+        self.cmds
+            .spawn((
+                SpatialBundle::from_transform(Transform::from_xyz(pos.x, POWERUP_HEIGHT, pos.z)),
+                PowerUp(kind),
+                PowerUpLifetime::new(lifetime),
+                Game,
+                Name::new(format!("{:?} PowerUp", kind)),
+            ))
+            .with_children(|parent| {
+                parent.spawn(model);
+                parent.spawn(light);
+            });
+    }
+}
+
+pub fn spawn_powerups(
+    mut spawner: PowerUpSpawner,
+    spawn_timer: Res<PowerUpSpawnTime>,
+    mut game_rng: ResMut<GameRng>,
+    level: Res<Level>,
+    grid: Res<NavGrid>,
+) {
+    // only draw from the stream when spawning, so the sequence doesn't depend on the tick count
+    if !spawn_timer.0.finished() {
+        return;
+    }
+
+    let rng = game_rng.stream(RngStream::PowerUps);
+
+    // keep trying points until one isn't inside a prop like a table or the bar
+    let pos = (0..POWERUP_SPAWN_ATTEMPTS)
+        .map(|_| Level::random_point(&level.powerup_spawns, rng))
+        .find(|pos| grid.is_open(*pos));
+
+    let Some(pos) = pos else { return };
+
+    // weighted pick, walking the kinds in a fixed order to stay deterministic
+    let config = &spawner.config;
+    let total: u32 = PowerUpKind::ALL
+        .iter()
+        .map(|kind| config.powerup(*kind).weight)
+        .sum();
+    if total == 0 {
+        return;
+    }
+
+    let mut roll = rng.gen_range(0..total);
+    let Some(kind) = PowerUpKind::ALL.into_iter().find(|kind| {
+        let weight = config.powerup(*kind).weight;
+        if roll < weight {
+            true
+        } else {
+            roll -= weight;
+            false
         }
+    }) else {
+        return;
     };
 
-    if spawn_timer.0.finished() {
-        let random_powerup = rng.gen_range(1..=3);
-
-        match random_powerup {
-            1 => {
-                cmds.spawn((
-                    powerup(Color::GREEN),
-                    StaminaPowerUp,
-                    Game,
-                    Name::new("Stamina PowerUp"),
-                ));
-            }
-            2 => {
-                cmds.spawn((
-                    powerup(Color::RED),
-                    HpPowerUp,
-                    Game,
-                    Name::new("Health PowerUp"),
-                ));
-            }
-            3 => {
-                cmds.spawn((
-                    powerup(Color::YELLOW),
-                    DamagePowerUp,
-                    Game,
-                    Name::new("Damage PowerUp"),
-                ));
-            }
-            _ => (),
-        }
-    }
+    spawner.spawn(kind, pos);
 }
 
-pub fn collect_stamina_powerup(
-    mut cmds: Commands,
-    assets: Res<AssetServer>,
-    mut player_q: Query<(&mut Stamina, &Transform), With<Player>>,
-    powerup_q: Query<(Entity, &Transform), With<StaminaPowerUp>>,
+/// Roll the loot table of every killed enemy, later waves drop more
+pub fn drop_loot(
+    mut spawner: PowerUpSpawner,
+    mut death_evr: EventReader<EnemyDeathEv>,
+    mut game_rng: ResMut<GameRng>,
+    director: Res<WaveDirector>,
 ) {
-    for (powerup_ent, powerup_trans) in powerup_q.iter() {
-        for (mut stamina, player_trans) in player_q.iter_mut() {
-            let distance = powerup_trans.translation.distance(player_trans.translation);
-
-            // collect powerup and despawn
-            if distance < PLAYER_SIZE {
-                stamina.value = stamina.max;
-                cmds.entity(powerup_ent).despawn_recursive();
-
-                // spawn txt
-                let txt = powerup_txt(&assets, "Full Stamina!".to_string());
-                cmds.spawn((
-                    txt,
-                    Name::new("Full Stamina Text"),
-                    PowerUpDisplay::default(),
-                    Game,
-                ));
-            }
+    let rng = game_rng.stream(RngStream::Loot);
+
+    for ev in death_evr.iter() {
+        let config = &spawner.config;
+        let scale = config
+            .powerups
+            .loot_curve
+            .apply(1.0, director.wave.saturating_sub(1));
+
+        let mut loot = config
+            .enemy(ev.kind)
+            .loot
+            .iter()
+            .filter(|(kind, _)| !(ev.nuked && *kind == PowerUpKind::Nuke));
+
+        // once the scaled chances add up past 1, shrink them back so every entry keeps its share
+        let total: f32 = loot.clone().map(|(_, chance)| chance * scale).sum();
+        let scale = if total > 1.0 { scale / total } else { scale };
+
+        // one roll per kill, every entry takes its slice of it
+        let mut roll: f32 = rng.gen();
+        let drop = loot.find(|(_, chance)| {
+            roll -= chance * scale;
+            roll < 0.0
+        });
+
+        if let Some((kind, _)) = drop {
+            spawner.spawn(*kind, ev.pos);
         }
     }
 }
 
-pub fn collect_dmg_powerup(
+/// Despawn powerups nobody picked up in time
+pub fn despawn_expired_powerups(
     mut cmds: Commands,
-    assets: Res<AssetServer>,
-    mut player_q: Query<(&Transform, &mut Damage), With<Player>>,
-    powerup_q: Query<(Entity, &Transform), With<DamagePowerUp>>,
-    mut duration_res: ResMut<DamageBoostDuration>,
+    time: Res<FixedTime>,
+    mut powerup_q: Query<(Entity, &mut PowerUpLifetime)>,
 ) {
-    for (powerup_ent, powerup_trans) in powerup_q.iter() {
-        for (player_trans, mut dmg) in player_q.iter_mut() {
-            let distance = powerup_trans.translation.distance(player_trans.translation);
-
-            // collect powerup and despawn
-            if distance < PLAYER_SIZE {
-                duration_res.0.reset();
-                duration_res.0.unpause();
-                cmds.entity(powerup_ent).despawn_recursive();
-
-                dmg.value = dmg.max + DMG_BOOST;
-
-                // spawn txt
-                let txt = powerup_txt(&assets, "x2 Damage!".to_string());
-                cmds.spawn((
-                    txt,
-                    Name::new("x2 Damage Text"),
-                    PowerUpDisplay::default(),
-                    Game,
-                ));
-            }
+    for (ent, mut lifetime) in powerup_q.iter_mut() {
+        lifetime.0.tick(time.period);
+
+        if lifetime.0.finished() {
+            cmds.entity(ent).despawn_recursive();
         }
     }
 }
 
-pub fn collect_hp_powerup(
-    mut cmds: Commands,
-    assets: Res<AssetServer>,
-    mut player_q: Query<(&Transform, &mut Hp), With<Player>>,
-    powerup_q: Query<(Entity, &Transform), With<HpPowerUp>>,
+/// Pull powerups inside the magnet radius toward the closest living player
+pub fn pull_powerups(
+    time: Res<FixedTime>,
+    config: Res<GameConfig>,
+    player_q: Query<&Transform, CollectingPlayer>,
+    mut powerup_q: Query<&mut Transform, (With<PowerUp>, Without<Player>)>,
+    grid: Res<NavGrid>,
 ) {
-    for (powerup_ent, powerup_trans) in powerup_q.iter() {
-        for (player_trans, mut hp) in player_q.iter_mut() {
-            let distance = powerup_trans.translation.distance(player_trans.translation);
-
-            // collect powerup and despawn
-            if distance < PLAYER_SIZE {
-                if hp.value + HP_BOOST > hp.max {
-                    hp.value = hp.max;
-                } else {
-                    hp.value += HP_BOOST;
-                }
+    let Some(magnet) = config.powerups.magnet else { return };
+
+    for mut powerup_trans in powerup_q.iter_mut() {
+        let closest = player_q
+            .iter()
+            .map(|player_trans| player_trans.translation - powerup_trans.translation)
+            .map(|offset| Vec3::new(offset.x, 0.0, offset.z))
+            .filter(|offset| offset.length() < magnet.radius)
+            .min_by(|a, b| a.length().total_cmp(&b.length()));
 
-                cmds.entity(powerup_ent).despawn_recursive();
+        if let Some(offset) = closest {
+            let step = magnet.speed * time.period.as_secs_f32();
+            let pulled =
+                powerup_trans.translation + offset.normalize_or_zero() * step.min(offset.length());
 
-                // spawn txt
-                let txt = powerup_txt(&assets, format!("+{} health!", HP_BOOST));
-                cmds.spawn((
-                    txt,
-                    Name::new("HP PowerUp Text"),
-                    PowerUpDisplay::default(),
-                    Game,
-                ));
+            // props stop the pull, the powerup waits on their side
+            if grid.is_open(pulled) {
+                powerup_trans.translation = pulled;
             }
         }
     }
 }
 
-pub fn spawn_dmg_powerup_duration_display(mut cmds: Commands, assets: Res<AssetServer>) {
-    let txt = TextBundle {
-        text: Text::from_section(
-            "",
-            TextStyle {
-                font: assets.load("fonts/PermanentMarker-Regular.ttf"),
-                font_size: 25.0,
-                color: Color::WHITE.into(),
-            },
-        ),
-        style: Style {
-            display: Display::None,
-            position_type: PositionType::Absolute,
-            left: Val::Percent(1.2),
-            top: Val::Percent(13.0),
-            ..default()
-        },
-        ..default()
-    };
+/// Spin and bob the powerup models, only the looks, the powerup itself stays put
+pub fn animate_powerups(time: Res<Time>, mut model_q: Query<&mut Transform, With<PowerUpModel>>) {
+    let elapsed = time.elapsed_seconds();
 
-    cmds.spawn((
-        txt,
-        Name::new("2X Damage Text"),
-        DamagePowerUpDurationDisplay,
-        Game,
-    ));
+    for mut trans in model_q.iter_mut() {
+        trans.translation.y = (elapsed * POWERUP_BOB_SPEED).sin() * POWERUP_BOB_HEIGHT;
+        trans.rotation = Quat::from_rotation_y(elapsed * POWERUP_SPIN_SPEED);
+    }
 }
 
-pub fn update_dmg_powerup_duration_display(
-    duration: Res<DamageBoostDuration>,
-    mut txt_q: Query<(&mut Text, &mut Style), With<DamagePowerUpDurationDisplay>>,
+/// Blink powerups that are about to despawn
+pub fn blink_powerups(
+    config: Res<GameConfig>,
+    mut powerup_q: Query<(&PowerUpLifetime, &mut Visibility)>,
 ) {
-    if let Ok((mut txt, mut style)) = txt_q.get_single_mut() {
-        if duration.0.percent() > 0.0 {
-            // un-hide txt
-            style.display = Display::Flex;
-
-            // update time
-            let time = duration.0.elapsed();
-            let time_left = duration.0.duration().saturating_sub(time).as_secs();
-            txt.sections[0].value = format!("X2 Damage: {}", time_left);
+    for (lifetime, mut visibility) in powerup_q.iter_mut() {
+        let remaining = lifetime.0.remaining_secs();
+
+        let blink_off = remaining < config.powerups.blink_time
+            && (remaining / POWERUP_BLINK_INTERVAL) as u32 % 2 == 1;
+
+        *visibility = if blink_off {
+            Visibility::Hidden
         } else {
-            // hide txt
-            style.display = Display::None;
-        }
+            Visibility::Inherited
+        };
     }
 }
 
-pub fn tick_dmg_duration_timer(
-    mut player_q: Query<&mut Damage, With<Player>>,
-    time: Res<Time>,
-    mut duration: ResMut<DamageBoostDuration>,
+/// Pick up powerups the players walk into and apply what they do
+pub fn collect_powerups(
+    mut cmds: Commands,
+    mut player_q: Query<CollectingPlayerQuery, CollectingPlayer>,
+    mut enemy_q: Query<AffectedEnemyQuery, AffectedEnemy>,
+    powerup_q: Query<(Entity, &Transform, &PowerUp)>,
+    config: Res<GameConfig>,
+    mut collected_evw: EventWriter<PowerUpCollected>,
+    mut death_evw: EventWriter<EnemyDeathEv>,
 ) {
-    duration.0.tick(time.delta());
+    for (powerup_ent, powerup_trans, powerup) in powerup_q.iter() {
+        let Some((player_ent, _, mut hp, mut stamina, mut effects, mut arsenal, mut cash)) =
+            player_q.iter_mut().find(|(_, trans, ..)| {
+                powerup_trans.translation.distance(trans.translation) < PLAYER_SIZE
+            })
+        else {
+            continue;
+        };
+
+        match config.powerup(powerup.0).pickup {
+            Pickup::Stamina => stamina.value = stamina.max.value,
+            Pickup::Heal { hp: heal } => hp.value = (hp.value + heal).min(hp.max.value),
+            Pickup::Effect(kind) => effects.apply(kind, config.effect(kind)),
+            Pickup::Shield { hp } => {
+                effects.apply(EffectKind::Shield, config.effect(EffectKind::Shield));
+                cmds.entity(player_ent).insert(Shield(hp));
+            }
+            Pickup::EnemyEffect(kind) => {
+                for (.., mut enemy_effects) in enemy_q.iter_mut() {
+                    enemy_effects.apply(kind, config.effect(kind));
+                }
+            }
+            Pickup::Nuke => {
+                for (enemy_ent, mut enemy_hp, enemy_trans, kind, _) in enemy_q.iter_mut() {
+                    // already dead, waiting to be despawned
+                    if enemy_hp.value <= 0.0 {
+                        continue;
+                    }
 
-    if duration.0.finished() {
-        if let Ok(mut dmg) = player_q.get_single_mut() {
-            dmg.value = dmg.max;
+                    enemy_hp.value = 0.0;
+                    death_evw.send(EnemyDeathEv {
+                        ent: enemy_ent,
+                        killer: player_ent,
+                        kind: *kind,
+                        pos: enemy_trans.translation,
+                        nuked: true,
+                    });
+                }
+            }
+            Pickup::Ammo { magazines } => {
+                for weapon in arsenal.weapons.iter_mut() {
+                    weapon.restock(magazines);
+                }
+            }
+            Pickup::Cash { amount } => cash.0 += amount,
         }
 
-        duration.0.reset();
-        duration.0.pause();
+        cmds.entity(powerup_ent).despawn_recursive();
+        collected_evw.send(PowerUpCollected {
+            player: player_ent,
+            kind: powerup.0,
+        });
+    }
+}
+
+pub fn count_powerups(
+    mut collected_evr: EventReader<PowerUpCollected>,
+    mut stats: ResMut<RunStats>,
+) {
+    stats.powerups += collected_evr.iter().count() as u32;
+}
+
+pub fn show_powerup_text(
+    mut cmds: Commands,
+    assets: Res<AssetServer>,
+    mut collected_evr: EventReader<PowerUpCollected>,
+    player_q: Query<&PlayerId>,
+    hud_q: Query<(Entity, &PlayerId), With<PlayerHud>>,
+    config: Res<GameConfig>,
+) {
+    for ev in collected_evr.iter() {
+        let Ok(id) = player_q.get(ev.player) else {
+            continue;
+        };
+
+        let txt = (
+            powerup_txt(&assets, config.powerup(ev.kind).text.clone()),
+            Name::new(format!("{:?} PowerUp Text", ev.kind)),
+            PowerUpDisplay::default(),
+        );
+        spawn_on_hud(&mut cmds, &hud_q, id, txt);
     }
 }
 
@@ -272,3 +420,17 @@ fn powerup_txt(assets: &Res<AssetServer>, txt: String) -> TextBundle {
         ..default()
     }
 }
+
+/// Powerup texts show up on the HUD of the player that collected the powerup
+fn spawn_on_hud(
+    cmds: &mut Commands,
+    hud_q: &Query<(Entity, &PlayerId), With<PlayerHud>>,
+    id: &PlayerId,
+    txt: impl Bundle,
+) {
+    if let Some((hud, _)) = hud_q.iter().find(|(_, hud_id)| *hud_id == id) {
+        cmds.entity(hud).with_children(|parent| {
+            parent.spawn(txt);
+        });
+    }
+}
@@ -2,33 +2,37 @@ use bevy::prelude::*;
 
 pub mod projectile_cmps;
 pub mod projectile_evs;
-pub mod projectile_res;
 mod projectile_sys;
 
-use crate::AppState;
+use crate::game::{stats::StatsSet, AddTickEvent, GameplaySet};
 use projectile_evs::*;
-use projectile_res::*;
 use projectile_sys::*;
 
-pub const PROJECTILE_SPEED: f32 = 25.0;
-pub const FIRE_RATE: u64 = 150;
+pub const HIT_MARKER_DURATION: f32 = 0.15;
+/// volleys fired per shot while the Multishot effect is up
+pub const MULTISHOT_COUNT: u32 = 3;
+/// degrees between multishot volleys
+pub const MULTISHOT_ANGLE: f32 = 10.0;
 
 pub struct ProjectilePlugin;
 
 impl Plugin for ProjectilePlugin {
     fn build(&self, app: &mut App) {
-        app.add_event::<HitEv>()
-            .add_event::<HitEv>()
-            .init_resource::<FireRate>()
+        app.add_tick_event::<HitEv>()
             .add_systems(
-                Update,
+                FixedUpdate,
                 (
-                    shoot_projectile.run_if(shoot_projectile_condition),
+                    shoot_projectile
+                        .run_if(shoot_projectile_condition)
+                        .after(StatsSet),
                     move_projectile,
-                    hit_enemy,
                     despawn_projectile,
                 )
-                    .run_if(in_state(AppState::Game)),
+                    .in_set(GameplaySet),
+            )
+            .add_systems(
+                Update,
+                (spawn_hit_marker, despawn_hit_marker).in_set(GameplaySet),
             );
     }
 }
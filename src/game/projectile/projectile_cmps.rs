@@ -1,6 +1,26 @@
 use bevy::prelude::*;
 
+use super::HIT_MARKER_DURATION;
+
 #[derive(Component)]
 pub struct Projectile {
+    /// player that fired the projectile
+    pub owner: Entity,
     pub direction: Vec3,
+    pub on_beat: bool,
+    pub dmg: f32,
+    pub speed: f32,
+}
+
+#[derive(Component)]
+pub struct HitMarker {
+    pub duration: Timer,
+}
+
+impl Default for HitMarker {
+    fn default() -> Self {
+        HitMarker {
+            duration: Timer::from_seconds(HIT_MARKER_DURATION, TimerMode::Once),
+        }
+    }
 }
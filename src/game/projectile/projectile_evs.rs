@@ -1,7 +1,15 @@
-use bevy::prelude::{Entity, Event};
+use bevy::prelude::{Entity, Event, Vec3};
 
 #[derive(Event)]
 pub struct HitEv {
+    /// collider entity that was hit
     pub ent: Entity,
+    /// player that fired the projectile
+    pub owner: Entity,
     pub dmg: f32,
+    pub on_beat: bool,
+    /// world position where the projectile struck the collider
+    pub point: Vec3,
+    /// surface normal of the collider at the contact point
+    pub normal: Vec3,
 }
@@ -2,68 +2,145 @@ use bevy::{
     audio::{Volume, VolumeLevel},
     prelude::*,
 };
+use bevy_rapier3d::prelude::*;
 use bevy_third_person_camera::ThirdPersonCamera;
+use rand::Rng;
 
 use crate::{
+    actions::actions_res::Action,
     debug::debug_res::EnableDebugMode,
     game::{
-        enemy::{enemy_cmps::Enemy, ENEMY_SIZE},
+        config::config_res::GameConfig,
+        effects::effects_cmps::{EffectKind, StatusEffects},
+        enemy::enemy_cmps::Enemy,
         game_cmps::{Damage, Game},
-        player::player_cmps::{IsShooting, Player},
-        world::MAP_SIZE,
+        game_res::{GameRng, RngStream},
+        music::music_res::BeatClock,
+        player::{
+            player_cmps::{IsShooting, LivingPlayer, Player, PlayerInput},
+            player_res::RhythmAccuracy,
+        },
+        weapons::{
+            weapons_cmps::{Arsenal, FireMode},
+            WEAPON_VOLUME,
+        },
+        world::world_res::Level,
     },
-    gamepad::gamepad_rcs::MyGamepad,
+    settings::settings_res::Settings,
 };
 
 use super::{
-    projectile_cmps::Projectile, projectile_evs::HitEv, projectile_res::FireRate, PROJECTILE_SPEED,
+    projectile_cmps::{HitMarker, Projectile},
+    projectile_evs::HitEv,
+    MULTISHOT_ANGLE, MULTISHOT_COUNT,
 };
 
+type ShootingPlayer<'a> = (
+    Entity,
+    &'a mut Transform,
+    &'a mut IsShooting,
+    &'a mut Arsenal,
+    &'a Damage,
+    &'a StatusEffects,
+    &'a PlayerInput,
+);
+
+/// Every player fires their equipped weapon from their own fire action, aiming with their camera
 pub fn shoot_projectile(
     mut cmds: Commands,
-    time: Res<Time>,
-    btns: Res<Input<GamepadButton>>,
-    mouse: Res<Input<MouseButton>>,
     assets: Res<AssetServer>,
-    mut fire_rate: ResMut<FireRate>,
+    mut accuracy: ResMut<RhythmAccuracy>,
+    beat_clock: Res<BeatClock>,
+    config: Res<GameConfig>,
+    settings: Res<Settings>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
-    my_gamepad: Option<Res<MyGamepad>>,
-    mut player_q: Query<(&mut Transform, &mut IsShooting), With<Player>>,
-    cam_q: Query<&Transform, (With<ThirdPersonCamera>, Without<Player>)>,
+    mut game_rng: ResMut<GameRng>,
+    mut player_q: Query<ShootingPlayer, LivingPlayer>,
 ) {
-    // return id of gamepad if one is connected
-    let gamepad = if let Some(gp) = my_gamepad {
-        Some(gp.gamepad)
-    } else {
-        None
-    };
+    for (player_ent, mut player_trans, mut is_shooting, mut arsenal, dmg, effects, input) in
+        player_q.iter_mut()
+    {
+        let actions = &input.actions;
+        let cam_trans = input.aim();
+
+        let pressed = actions.pressed(Action::Fire);
+        let just_pressed = actions.just_pressed(Action::Fire);
+
+        let weapon = arsenal.current_mut();
+
+        // decide if the trigger wants a shot this frame based on the fire mode
+        let wants_fire = match weapon.stats.fire_mode {
+            FireMode::Semi => just_pressed,
+            FireMode::Auto => pressed,
+            FireMode::Burst(shots) => {
+                if just_pressed && weapon.burst == 0 {
+                    weapon.burst = shots;
+                }
+                weapon.burst > 0
+            }
+        };
+
+        is_shooting.0 = pressed || weapon.burst > 0;
+
+        if !wants_fire || !weapon.can_fire() {
+            continue;
+        }
+
+        weapon.fire();
+        weapon.burst = weapon.burst.saturating_sub(1);
+
+        let direction = Vec3::new(cam_trans.back().x, cam_trans.back().y, cam_trans.back().z);
+
+        let mut projectile_transform = player_trans.clone();
+
+        // because the gun is not directly center mass of a player, an offset needs to be applied
+        let mut offset = cam_trans.left() / 6.0;
+        offset -= cam_trans.forward() / 1.5; // gun barrel length
+        projectile_transform.translation -= offset;
 
-    if let Ok((mut player_trans, mut is_shooting)) = player_q.get_single_mut() {
-        let Ok(cam_trans) = cam_q.get_single() else { return };
+        // shots landing close enough to a beat deal bonus damage
+        let on_beat = beat_clock.is_on_beat(config.rhythm.on_beat_window);
+        accuracy.shots += 1;
+        if on_beat {
+            accuracy.on_beat += 1;
+        }
 
-        let right_trigger = if let Some(g) = gamepad {
-            btns.pressed(GamepadButton::new(g, GamepadButtonType::RightTrigger2))
+        let color = if on_beat {
+            Color::GOLD
         } else {
-            false
+            Color::ORANGE_RED
         };
 
-        if right_trigger || mouse.pressed(MouseButton::Left) {
-            if fire_rate.0.finished() || fire_rate.0.percent_left() == 1.0 {
-                let direction =
-                    Vec3::new(cam_trans.back().x, cam_trans.back().y, cam_trans.back().z);
+        // damage modifiers scale every weapon by the same factor
+        let mut projectile_dmg = weapon.stats.damage * dmg.0.factor();
+        if on_beat {
+            projectile_dmg += config.rhythm.on_beat_dmg_bonus;
+        }
+
+        let rng = game_rng.stream(RngStream::Weapons);
+        let half_spread = weapon.stats.spread.to_radians() / 2.0;
+
+        // multishot fans extra volleys out to both sides of the aim
+        let volleys = if effects.has(EffectKind::Multishot) {
+            MULTISHOT_COUNT
+        } else {
+            1
+        };
 
-                let mut projectile_transform = player_trans.clone();
+        for volley in 0..volleys {
+            let fan = (volley as f32 - (volleys - 1) as f32 / 2.0) * MULTISHOT_ANGLE.to_radians();
 
-                // because the gun is not directly center mass of a player, an offset needs to be applied
-                let mut offset = cam_trans.left() / 6.0;
-                offset -= cam_trans.forward() / 1.5; // gun barrel length
-                projectile_transform.translation -= offset;
+            for _ in 0..weapon.stats.projectiles {
+                // scatter each projectile inside the weapon's spread cone
+                let yaw = fan + rng.gen_range(-half_spread..=half_spread);
+                let pitch = rng.gen_range(-half_spread..=half_spread);
+                let direction = Quat::from_euler(EulerRot::YXZ, yaw, pitch, 0.0) * direction;
 
                 let projectile = (
                     PbrBundle {
                         material: materials.add(StandardMaterial {
-                            emissive: Color::ORANGE_RED.into(),
+                            emissive: color,
                             ..default()
                         }),
                         mesh: meshes.add(Mesh::from(shape::UVSphere {
@@ -73,32 +150,31 @@ pub fn shoot_projectile(
                         transform: Transform::from_translation(projectile_transform.translation),
                         ..default()
                     },
-                    Projectile { direction },
+                    Projectile {
+                        owner: player_ent,
+                        direction,
+                        on_beat,
+                        dmg: projectile_dmg,
+                        speed: weapon.stats.projectile_speed,
+                    },
                     Game,
                 );
 
                 cmds.spawn(projectile);
-
-                // rotate player in direction he is shooting
-                player_trans.look_to(-direction, Vec3::Y);
-
-                cmds.spawn(AudioBundle {
-                    source: assets.load(r"audio\shoot.ogg"),
-                    settings: PlaybackSettings {
-                        volume: Volume::Relative(VolumeLevel::new(0.5)),
-                        ..default()
-                    },
-                    ..default()
-                });
-
-                is_shooting.0 = true;
             }
-
-            fire_rate.0.tick(time.delta());
-        } else {
-            fire_rate.0.reset();
-            is_shooting.0 = false;
         }
+
+        // rotate player in direction he is shooting
+        player_trans.look_to(-direction, Vec3::Y);
+
+        cmds.spawn(AudioBundle {
+            source: assets.load(&weapon.stats.sound),
+            settings: PlaybackSettings {
+                volume: Volume::Relative(VolumeLevel::new(WEAPON_VOLUME * settings.sfx_volume())),
+                ..default()
+            },
+            ..default()
+        });
     }
 }
 
@@ -120,43 +196,102 @@ pub fn shoot_projectile_condition(
     }
 }
 
+/// Ray cast along the distance each projectile travels this frame so it stops at the first
+/// collider in its way (walls, props or enemies) instead of tunneling through it
+/// Fire hit event when the collider is an enemy, projectiles pass through every player
 pub fn move_projectile(
-    time: Res<Time>,
-    mut projectile_q: Query<(&mut Transform, &Projectile), With<Projectile>>,
+    mut cmds: Commands,
+    time: Res<FixedTime>,
+    rapier_context: Res<RapierContext>,
+    mut hit_evw: EventWriter<HitEv>,
+    player_q: Query<(), With<Player>>,
+    enemy_q: Query<(), With<Enemy>>,
+    mut projectile_q: Query<(Entity, &mut Transform, &Projectile), Without<Player>>,
 ) {
-    for (mut trans, projectile) in projectile_q.iter_mut() {
-        trans.translation -=
-            projectile.direction.normalize() * PROJECTILE_SPEED * time.delta_seconds();
+    let not_player = |ent| !player_q.contains(ent);
+    let filter = QueryFilter::default()
+        .exclude_sensors()
+        .predicate(&not_player);
+
+    for (projectile_ent, mut trans, projectile) in projectile_q.iter_mut() {
+        let direction = -projectile.direction.normalize();
+        let distance = projectile.speed * time.period.as_secs_f32();
+
+        let hit = rapier_context.cast_ray_and_get_normal(
+            trans.translation,
+            direction,
+            distance,
+            true,
+            filter,
+        );
+
+        let Some((hit_ent, intersection)) = hit else {
+            trans.translation += direction * distance;
+            continue;
+        };
+
+        // reduce enemy hp
+        if enemy_q.contains(hit_ent) {
+            // fire hit event
+            hit_evw.send(HitEv {
+                ent: hit_ent,
+                owner: projectile.owner,
+                dmg: projectile.dmg,
+                on_beat: projectile.on_beat,
+                point: intersection.point,
+                normal: intersection.normal,
+            });
+        }
+
+        cmds.entity(projectile_ent).despawn_recursive();
     }
 }
 
-/// Detect projectile-enemy collision
-/// Fire hit event
-pub fn hit_enemy(
+/// Flash a small marker where a projectile hit an enemy
+pub fn spawn_hit_marker(
     mut cmds: Commands,
-    mut hit_evw: EventWriter<HitEv>,
-    player_q: Query<&Damage, (With<Player>, Without<Enemy>)>,
-    enemy_q: Query<(Entity, &Transform), With<Enemy>>,
-    projectile_q: Query<(Entity, &Transform), With<Projectile>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut hit_evr: EventReader<HitEv>,
 ) {
-    for (enemy_ent, enemy_trans) in enemy_q.iter() {
-        for (projectile_ent, projectile_trans) in projectile_q.iter() {
-            let distance = enemy_trans
-                .translation
-                .distance(projectile_trans.translation);
-
-            let dmg = player_q.get_single().unwrap();
-
-            // reduce enemy hp and despawn projectile
-            if distance < ENEMY_SIZE {
-                // fire hit event
-                hit_evw.send(HitEv {
-                    dmg: dmg.value,
-                    ent: enemy_ent,
-                });
-
-                cmds.entity(projectile_ent).despawn_recursive();
-            }
+    for ev in hit_evr.iter() {
+        let color = if ev.on_beat {
+            Color::GOLD
+        } else {
+            Color::WHITE
+        };
+
+        cmds.spawn((
+            PbrBundle {
+                material: materials.add(StandardMaterial {
+                    emissive: color,
+                    ..default()
+                }),
+                mesh: meshes.add(Mesh::from(shape::UVSphere {
+                    radius: 0.05,
+                    ..default()
+                })),
+                // nudge off the surface so the marker isn't buried in the collider
+                transform: Transform::from_translation(ev.point + ev.normal * 0.05),
+                ..default()
+            },
+            HitMarker::default(),
+            Game,
+            Name::new("Hit Marker"),
+        ));
+    }
+}
+
+pub fn despawn_hit_marker(
+    mut cmds: Commands,
+    time: Res<Time>,
+    mut marker_q: Query<(Entity, &mut HitMarker)>,
+) {
+    for (ent, mut marker) in marker_q.iter_mut() {
+        marker.duration.tick(time.delta());
+
+        if marker.duration.finished() {
+            cmds.entity(ent).despawn_recursive();
         }
     }
 }
@@ -165,10 +300,10 @@ pub fn hit_enemy(
 pub fn despawn_projectile(
     mut cmds: Commands,
     projectile_q: Query<(Entity, &Transform), With<Projectile>>,
+    level: Res<Level>,
 ) {
     for (ent, trans) in projectile_q.iter() {
-        if trans.translation.x.abs() > MAP_SIZE / 2.0 || trans.translation.z.abs() > MAP_SIZE / 2.0
-        {
+        if !level.contains(trans.translation) {
             cmds.entity(ent).despawn_recursive();
         }
     }
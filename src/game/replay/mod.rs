@@ -0,0 +1,87 @@
+use bevy::prelude::*;
+
+pub mod replay_res;
+mod replay_sys;
+
+use replay_res::*;
+use replay_sys::*;
+
+use crate::{
+    game::{
+        bot::bot_res::BotPlayers, game_res::Headless, game_sys::seed_game_rng, GameplaySet,
+        RunSetupSet, TickStartSet,
+    },
+    AppState,
+};
+
+pub const REPLAYS_DIR: &str = "replays";
+pub const REPLAY_EXTENSION: &str = "replay";
+/// bumped whenever old replays can no longer be played back
+pub const REPLAY_VERSION: u32 = 3;
+
+/// Systems that write every player's input for the tick, before it is recorded
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SampleInputSet;
+
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GameTick>()
+            .init_resource::<Recording>()
+            .configure_set(FixedUpdate, SampleInputSet.in_set(TickStartSet))
+            .add_systems(
+                OnEnter(AppState::Game),
+                (
+                    start_playback.run_if(resource_exists::<Playback>()),
+                    start_recording,
+                )
+                    .chain()
+                    .after(seed_game_rng)
+                    .in_set(RunSetupSet),
+            )
+            .add_systems(
+                FixedUpdate,
+                (
+                    (
+                        sample_player_input
+                            .run_if(not(resource_exists::<Playback>()))
+                            .run_if(not(resource_exists::<Headless>())),
+                        play_back_input.run_if(resource_exists::<Playback>()),
+                    )
+                        .in_set(SampleInputSet),
+                    (record_player_input, advance_game_tick)
+                        .chain()
+                        .after(SampleInputSet),
+                )
+                    .in_set(TickStartSet),
+            )
+            .add_systems(
+                FixedUpdate,
+                end_playback
+                    .run_if(resource_exists::<Playback>())
+                    .in_set(GameplaySet),
+            )
+            .add_systems(
+                PreUpdate,
+                show_playback_aim
+                    .run_if(in_state(AppState::Game))
+                    .run_if(resource_exists::<Playback>().or_else(resource_exists::<BotPlayers>())),
+            )
+            .add_systems(
+                Update,
+                play_replay_from_menu
+                    .run_if(in_state(AppState::MainMenu))
+                    .run_if(resource_exists::<Playback>()),
+            )
+            .add_systems(
+                OnEnter(AppState::GameOver),
+                (
+                    save_replay
+                        .run_if(not(resource_exists::<Playback>()))
+                        .run_if(not(resource_exists::<Headless>())),
+                    check_playback.run_if(resource_exists::<Playback>()),
+                ),
+            );
+    }
+}
@@ -0,0 +1,106 @@
+use std::path::{Path, PathBuf};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    game::{game_res::GameMode, player::player_cmps::PlayerInput},
+    storage::{load_ron_gz, project_dirs, save_ron_gz},
+};
+
+use super::{REPLAYS_DIR, REPLAY_EXTENSION, REPLAY_VERSION};
+
+/// New input of a single player, starting on the given tick
+#[derive(Serialize, Deserialize, Clone)]
+pub struct InputChange {
+    pub tick: u32,
+    pub player: usize,
+    pub input: PlayerInput,
+}
+
+/// Everything needed to play a run again: its seed, its players and their input
+/// Input is only stored on the ticks it changed to keep the file small
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct Replay {
+    pub version: u32,
+    pub seed: u64,
+    pub mode: GameMode,
+    pub players: usize,
+    /// name of the level the run was played on
+    pub level: String,
+    /// gameplay ticks the run lasted
+    pub ticks: u32,
+    /// kill count the run ended with, a playback that ends differently went out of sync
+    pub kills: u32,
+    pub inputs: Vec<InputChange>,
+}
+
+impl Replay {
+    pub fn new(seed: u64, mode: GameMode, players: usize, level: String) -> Self {
+        Self {
+            version: REPLAY_VERSION,
+            seed,
+            mode,
+            players,
+            level,
+            ..default()
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let replay: Replay = load_ron_gz(path)?;
+
+        if replay.version != REPLAY_VERSION {
+            return Err(format!(
+                "replay version {} is not supported, expected {}",
+                replay.version, REPLAY_VERSION
+            ));
+        }
+
+        Ok(replay)
+    }
+
+    /// Save into the replays folder, named after the current date & time
+    pub fn save(&self) -> Result<PathBuf, String> {
+        let dir = project_dirs()
+            .map(|dirs| dirs.data_dir().join(REPLAYS_DIR))
+            .ok_or("no data folder")?;
+
+        let name = chrono::Local::now().format("%Y-%m-%d_%H-%M-%S");
+        let path = dir.join(format!("{}.{}", name, REPLAY_EXTENSION));
+
+        save_ron_gz(&path, self).map(|_| path)
+    }
+}
+
+/// Replay of the run in progress
+#[derive(Resource, Default)]
+pub struct Recording {
+    pub replay: Replay,
+    /// last recorded input of every player
+    pub last: Vec<PlayerInput>,
+}
+
+/// Present while a replay is played back, players then follow the replay instead of their devices
+#[derive(Resource)]
+pub struct Playback {
+    pub replay: Replay,
+    /// index of the next input change to apply
+    pub next: usize,
+    /// set once the replay started, leaving it for the main menu ends the playback
+    pub started: bool,
+}
+
+impl Playback {
+    pub fn new(replay: Replay) -> Self {
+        Self {
+            replay,
+            next: 0,
+            started: false,
+        }
+    }
+}
+
+/// Gameplay ticks since the run started
+#[derive(Resource, Default)]
+pub struct GameTick(pub u32);
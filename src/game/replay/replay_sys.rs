@@ -0,0 +1,194 @@
+use bevy::prelude::*;
+
+use crate::{
+    actions::actions_res::{DeviceActions, InputSource, PendingPresses},
+    game::{
+        bot::bot_cmps::BotController,
+        config::config_res::GameConfig,
+        game_evs::GameOver,
+        game_res::{GameMode, GameRng},
+        player::{
+            player_cmps::{ControlledBy, PlayerCam, PlayerId, PlayerInput},
+            player_res::{KillCount, Party},
+        },
+        world::world_res::{Level, Levels, SelectedLevel},
+    },
+    AppState,
+};
+
+use super::replay_res::*;
+
+/// Play the replay given with `--replay` once the game is ready, back in the menu afterwards it ends
+pub fn play_replay_from_menu(
+    mut cmds: Commands,
+    mut playback: ResMut<Playback>,
+    config: Option<Res<GameConfig>>,
+    level: Option<Res<Level>>,
+    levels: Res<Levels>,
+    mut selected: ResMut<SelectedLevel>,
+    mut next_app_state: ResMut<NextState<AppState>>,
+) {
+    if playback.started {
+        cmds.remove_resource::<Playback>();
+    } else if config.is_some() && level.is_some() {
+        if levels.find(&playback.replay.level).is_none() {
+            error!(
+                "Could not play the replay, there is no level named {}",
+                playback.replay.level
+            );
+            cmds.remove_resource::<Playback>();
+            return;
+        }
+
+        selected.0 = playback.replay.level.clone();
+        playback.started = true;
+        next_app_state.set(AppState::Game);
+    }
+}
+
+/// Set the run up like the recorded one
+pub fn start_playback(
+    mut playback: ResMut<Playback>,
+    mut game_rng: ResMut<GameRng>,
+    mut party: ResMut<Party>,
+    mut mode: ResMut<GameMode>,
+) {
+    playback.next = 0;
+    game_rng.seed_with(playback.replay.seed);
+    party.0 = vec![InputSource::Any; playback.replay.players];
+    *mode = playback.replay.mode;
+}
+
+pub fn start_recording(
+    mut recording: ResMut<Recording>,
+    mut tick: ResMut<GameTick>,
+    game_rng: Res<GameRng>,
+    party: Res<Party>,
+    mode: Res<GameMode>,
+    level: Res<Level>,
+) {
+    let players = party.0.len();
+
+    recording.replay = Replay::new(game_rng.seed, *mode, players, level.name.clone());
+    recording.last = vec![PlayerInput::default(); players];
+    tick.0 = 0;
+}
+
+/// Sample every player's device and camera into their input for this tick
+pub fn sample_player_input(
+    device_actions: Res<DeviceActions>,
+    mut pending: ResMut<PendingPresses>,
+    mut player_q: Query<(&mut PlayerInput, &ControlledBy, &PlayerId), Without<BotController>>,
+    cam_q: Query<(&Transform, &PlayerId), PlayerCam>,
+) {
+    for (mut input, controlled_by, id) in player_q.iter_mut() {
+        let source = controlled_by.0;
+
+        if let Some(actions) = device_actions.get(source) {
+            input.actions = actions.with_presses(pending.get(source));
+        }
+
+        if let Some((cam, _)) = cam_q.iter().find(|(_, cam_id)| *cam_id == id) {
+            input.aim = cam.rotation;
+        }
+    }
+
+    pending.clear();
+}
+
+/// Apply the recorded input changes of this tick
+pub fn play_back_input(
+    tick: Res<GameTick>,
+    mut playback: ResMut<Playback>,
+    mut player_q: Query<(&mut PlayerInput, &PlayerId)>,
+) {
+    while let Some(change) = playback.replay.inputs.get(playback.next) {
+        if change.tick > tick.0 {
+            break;
+        }
+
+        for (mut input, id) in player_q.iter_mut() {
+            if id.0 == change.player {
+                *input = change.input;
+            }
+        }
+
+        playback.next += 1;
+    }
+}
+
+/// Store the input of every player that changed since the last tick
+pub fn record_player_input(
+    tick: Res<GameTick>,
+    mut recording: ResMut<Recording>,
+    player_q: Query<(&PlayerInput, &PlayerId)>,
+) {
+    for (input, id) in player_q.iter() {
+        let Some(last) = recording.last.get_mut(id.0) else {
+            continue;
+        };
+
+        if last == input {
+            continue;
+        }
+
+        *last = *input;
+        recording.replay.inputs.push(InputChange {
+            tick: tick.0,
+            player: id.0,
+            input: *input,
+        });
+    }
+}
+
+pub fn advance_game_tick(mut tick: ResMut<GameTick>) {
+    tick.0 += 1;
+}
+
+/// A replay that outlives its recording went out of sync, end it where the recording ended
+pub fn end_playback(
+    tick: Res<GameTick>,
+    playback: Res<Playback>,
+    mut game_over_evw: EventWriter<GameOver>,
+) {
+    if tick.0 > playback.replay.ticks {
+        warn!("Replay went out of sync, it outlasted the recorded run");
+        game_over_evw.send(GameOver);
+    }
+}
+
+/// Turn the cameras like they were turned in the recorded run, or where the bots aim
+pub fn show_playback_aim(
+    player_q: Query<(&PlayerInput, &PlayerId)>,
+    mut cam_q: Query<(&mut Transform, &PlayerId), PlayerCam>,
+) {
+    for (mut cam, cam_id) in cam_q.iter_mut() {
+        if let Some((input, _)) = player_q.iter().find(|(_, id)| *id == cam_id) {
+            cam.rotation = input.aim;
+        }
+    }
+}
+
+pub fn save_replay(tick: Res<GameTick>, kills: Res<KillCount>, mut recording: ResMut<Recording>) {
+    recording.replay.ticks = tick.0;
+    recording.replay.kills = kills.0;
+
+    match recording.replay.save() {
+        Ok(path) => info!("Saved replay to {}", path.display()),
+        Err(e) => warn!("Could not save replay: {}", e),
+    }
+}
+
+/// Compare the end of the playback with the recorded run
+pub fn check_playback(tick: Res<GameTick>, kills: Res<KillCount>, playback: Res<Playback>) {
+    let replay = &playback.replay;
+
+    if kills.0 == replay.kills && tick.0 == replay.ticks {
+        info!("Replay ended in sync with {} kills", kills.0);
+    } else {
+        warn!(
+            "Replay went out of sync: {} kills after {} ticks, recorded {} kills after {} ticks",
+            kills.0, tick.0, replay.kills, replay.ticks
+        );
+    }
+}
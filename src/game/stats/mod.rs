@@ -0,0 +1,23 @@
+use bevy::prelude::*;
+
+pub mod stats_cmps;
+mod stats_sys;
+
+use stats_sys::*;
+
+use crate::game::GameplaySet;
+
+/// Systems recomputing attributes from their modifiers, order modifier changes before this set
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StatsSet;
+
+pub struct StatsPlugin;
+
+impl Plugin for StatsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            recompute_stats.in_set(StatsSet).in_set(GameplaySet),
+        );
+    }
+}
@@ -0,0 +1,110 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::game::effects::effects_cmps::EffectKind;
+
+/// A stat made of a base value and the modifiers on top of it
+/// `value` is recomputed from the entity's modifiers every tick, change `base` instead
+#[derive(Clone, Copy)]
+pub struct Attribute {
+    pub base: f32,
+    pub value: f32,
+}
+
+impl Attribute {
+    pub fn new(base: f32) -> Self {
+        Self { base, value: base }
+    }
+
+    /// How much the modifiers scale the base, 1.0 if there is no base to scale
+    pub fn factor(&self) -> f32 {
+        if self.base > 0.0 {
+            self.value / self.base
+        } else {
+            1.0
+        }
+    }
+}
+
+/// Multiplier on fire & attack rate, higher fires faster
+#[derive(Component)]
+pub struct FireRate(pub Attribute);
+
+impl Default for FireRate {
+    fn default() -> Self {
+        Self(Attribute::new(1.0))
+    }
+}
+
+impl FireRate {
+    /// Nothing fires at all, like a Frozen enemy
+    pub fn is_stopped(&self) -> bool {
+        self.0.value <= 0.0
+    }
+}
+
+/// Multiplier on incoming damage
+#[derive(Component)]
+pub struct DamageTaken(pub Attribute);
+
+impl Default for DamageTaken {
+    fn default() -> Self {
+        Self(Attribute::new(1.0))
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Stat {
+    Damage,
+    Speed,
+    MaxHp,
+    MaxStamina,
+    StaminaRegen,
+    FireRate,
+    DamageTaken,
+}
+
+#[derive(Deserialize, Clone, Copy)]
+pub enum ModifierOp {
+    Add(f32),
+    Mul(f32),
+}
+
+/// What put a modifier on, so it can be taken off again
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ModifierSource {
+    Effect(EffectKind),
+}
+
+pub struct Modifier {
+    pub source: ModifierSource,
+    pub stat: Stat,
+    pub op: ModifierOp,
+}
+
+/// Every modifier on a player's or enemy's stats
+#[derive(Component, Default)]
+pub struct Modifiers(pub Vec<Modifier>);
+
+impl Modifiers {
+    pub fn add(&mut self, source: ModifierSource, stat: Stat, op: ModifierOp) {
+        self.0.push(Modifier { source, stat, op });
+    }
+
+    pub fn remove_where(&mut self, remove: impl Fn(ModifierSource) -> bool) {
+        self.0.retain(|modifier| !remove(modifier.source));
+    }
+
+    /// Base plus every additive modifier, times every multiplier
+    pub fn apply(&self, stat: Stat, attribute: &mut Attribute) {
+        let (add, mul) = self.0.iter().filter(|modifier| modifier.stat == stat).fold(
+            (0.0, 1.0),
+            |(add, mul), modifier| match modifier.op {
+                ModifierOp::Add(value) => (add + value, mul),
+                ModifierOp::Mul(value) => (add, mul * value),
+            },
+        );
+
+        attribute.value = ((attribute.base + add) * mul).max(0.0);
+    }
+}
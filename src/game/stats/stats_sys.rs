@@ -0,0 +1,51 @@
+use bevy::prelude::*;
+
+use crate::game::{
+    game_cmps::{Damage, Hp, Speed},
+    player::player_cmps::Stamina,
+};
+
+use super::stats_cmps::*;
+
+type StatsQuery<'a> = (
+    &'a Modifiers,
+    Option<&'a mut Hp>,
+    Option<&'a mut Damage>,
+    Option<&'a mut Speed>,
+    Option<&'a mut Stamina>,
+    Option<&'a mut FireRate>,
+    Option<&'a mut DamageTaken>,
+);
+
+/// Recompute the final value of every attribute from its base and modifiers
+/// Hp & stamina are kept within their new max
+pub fn recompute_stats(mut stats_q: Query<StatsQuery>) {
+    for (modifiers, hp, damage, speed, stamina, fire_rate, damage_taken) in stats_q.iter_mut() {
+        if let Some(mut hp) = hp {
+            modifiers.apply(Stat::MaxHp, &mut hp.max);
+            hp.value = hp.value.min(hp.max.value);
+        }
+
+        if let Some(mut damage) = damage {
+            modifiers.apply(Stat::Damage, &mut damage.0);
+        }
+
+        if let Some(mut speed) = speed {
+            modifiers.apply(Stat::Speed, &mut speed.0);
+        }
+
+        if let Some(mut stamina) = stamina {
+            modifiers.apply(Stat::MaxStamina, &mut stamina.max);
+            modifiers.apply(Stat::StaminaRegen, &mut stamina.regen);
+            stamina.value = stamina.value.min(stamina.max.value);
+        }
+
+        if let Some(mut fire_rate) = fire_rate {
+            modifiers.apply(Stat::FireRate, &mut fire_rate.0);
+        }
+
+        if let Some(mut damage_taken) = damage_taken {
+            modifiers.apply(Stat::DamageTaken, &mut damage_taken.0);
+        }
+    }
+}
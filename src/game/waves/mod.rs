@@ -0,0 +1,36 @@
+use bevy::prelude::*;
+
+pub mod waves_evs;
+pub mod waves_res;
+mod waves_sys;
+
+use waves_evs::*;
+use waves_res::*;
+use waves_sys::*;
+
+use crate::{
+    game::{enemy::EnemySpawnSet, AddTickEvent, GameplaySet},
+    AppState,
+};
+
+pub const FIRST_WAVE_DELAY: f32 = 3.0;
+
+pub struct WavesPlugin;
+
+impl Plugin for WavesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WaveDirector>()
+            .add_tick_event::<WaveStarted>()
+            .add_tick_event::<WaveCleared>()
+            .add_systems(OnEnter(AppState::Game), reset_waves)
+            .add_systems(
+                FixedUpdate,
+                (
+                    start_wave,
+                    // the enemies queued this tick have to exist before counting who's left
+                    (apply_deferred, clear_wave).chain().after(EnemySpawnSet),
+                )
+                    .in_set(GameplaySet),
+            );
+    }
+}
@@ -0,0 +1,9 @@
+use bevy::prelude::Event;
+
+/// Fired with the wave number when a wave starts spawning
+#[derive(Event)]
+pub struct WaveStarted(pub u32);
+
+/// Fired with the wave number once every enemy of the wave is dead
+#[derive(Event)]
+pub struct WaveCleared(pub u32);
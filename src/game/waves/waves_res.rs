@@ -0,0 +1,151 @@
+use bevy::prelude::*;
+use rand::{
+    distributions::{Distribution, WeightedIndex},
+    Rng,
+};
+use serde::Deserialize;
+
+use crate::game::enemy::enemy_cmps::EnemyKind;
+
+use super::FIRST_WAVE_DELAY;
+
+/// Wave layout, part of the gameplay config
+#[derive(Deserialize, Clone)]
+pub struct WaveConfig {
+    /// seconds between clearing a wave and starting the next one
+    pub intermission: f32,
+    pub waves: Vec<WaveSpec>,
+    /// curves applied to the last wave once the authored waves run out
+    pub enemies_curve: Curve,
+    pub spawn_beats_curve: Curve,
+    pub hp_curve: Curve,
+    pub speed_curve: Curve,
+}
+
+impl WaveConfig {
+    /// Resolve a wave by its number (starting at 1)
+    pub fn wave(&self, number: u32) -> WaveSpec {
+        let Some(last) = self.waves.last() else {
+            return WaveSpec::default();
+        };
+
+        let idx = number.saturating_sub(1) as usize;
+        if let Some(wave) = self.waves.get(idx) {
+            return wave.clone();
+        }
+
+        // endless waves past the authored ones
+        let extra = (idx + 1 - self.waves.len()) as u32;
+        WaveSpec {
+            enemies: self.enemies_curve.apply(last.enemies as f32, extra).round() as u32,
+            spawn_beats: (self
+                .spawn_beats_curve
+                .apply(last.spawn_beats as f32, extra)
+                .round() as u32)
+                .max(1),
+            hp: self.hp_curve.apply(last.hp, extra),
+            speed: self.speed_curve.apply(last.speed, extra),
+            composition: last.composition.clone(),
+        }
+    }
+}
+
+#[derive(Deserialize, Clone)]
+pub struct WaveSpec {
+    pub enemies: u32,
+    /// spawn an enemy every this many beats
+    pub spawn_beats: u32,
+    /// multiplier on the base enemy hp
+    pub hp: f32,
+    /// multiplier on the base enemy speed
+    pub speed: f32,
+    /// enemy kinds of the wave and their relative spawn weights
+    #[serde(default = "default_composition")]
+    pub composition: Vec<(EnemyKind, u32)>,
+}
+
+impl Default for WaveSpec {
+    fn default() -> Self {
+        Self {
+            enemies: 0,
+            spawn_beats: 4,
+            hp: 1.0,
+            speed: 1.0,
+            composition: default_composition(),
+        }
+    }
+}
+
+impl WaveSpec {
+    /// Pick a random enemy kind based on the wave composition
+    pub fn pick_enemy(&self, rng: &mut impl Rng) -> EnemyKind {
+        let weights = self.composition.iter().map(|(_, weight)| *weight);
+        let Ok(dist) = WeightedIndex::new(weights) else {
+            return EnemyKind::Walker;
+        };
+
+        self.composition[dist.sample(rng)].0
+    }
+}
+
+fn default_composition() -> Vec<(EnemyKind, u32)> {
+    vec![(EnemyKind::Walker, 1)]
+}
+
+/// How a wave value grows for every wave past the authored ones
+#[derive(Deserialize, Clone, Copy)]
+pub enum Curve {
+    Flat,
+    /// adds the amount per wave
+    Linear(f32),
+    /// multiplies by the factor per wave
+    Exponential(f32),
+}
+
+impl Curve {
+    pub fn apply(&self, base: f32, waves: u32) -> f32 {
+        match *self {
+            Curve::Flat => base,
+            Curve::Linear(amount) => base + amount * waves as f32,
+            Curve::Exponential(factor) => base * factor.powi(waves as i32),
+        }
+    }
+}
+
+#[derive(PartialEq)]
+pub enum WavePhase {
+    Intermission,
+    Active,
+}
+
+/// Tracks the current wave and how many of its enemies are left to spawn
+#[derive(Resource)]
+pub struct WaveDirector {
+    /// current wave number, 0 before the first wave
+    pub wave: u32,
+    pub phase: WavePhase,
+    pub intermission: Timer,
+    pub spec: WaveSpec,
+    pub remaining: u32,
+}
+
+impl Default for WaveDirector {
+    fn default() -> Self {
+        Self {
+            wave: 0,
+            phase: WavePhase::Intermission,
+            intermission: Timer::from_seconds(FIRST_WAVE_DELAY, TimerMode::Once),
+            spec: WaveSpec::default(),
+            remaining: 0,
+        }
+    }
+}
+
+impl WaveDirector {
+    /// Whether an enemy should spawn on this beat
+    pub fn should_spawn(&self, beat: u32) -> bool {
+        self.phase == WavePhase::Active
+            && self.remaining > 0
+            && beat.is_multiple_of(self.spec.spawn_beats.max(1))
+    }
+}
@@ -0,0 +1,52 @@
+use bevy::prelude::*;
+
+use crate::game::{config::config_res::GameConfig, enemy::enemy_cmps::Enemy};
+
+use super::{waves_evs::*, waves_res::*};
+
+/// Start over from the first wave whenever a game starts
+pub fn reset_waves(mut director: ResMut<WaveDirector>) {
+    *director = WaveDirector::default();
+}
+
+/// Start the next wave once the intermission is over
+pub fn start_wave(
+    time: Res<FixedTime>,
+    mut director: ResMut<WaveDirector>,
+    mut wave_started_evw: EventWriter<WaveStarted>,
+    config: Res<GameConfig>,
+) {
+    if director.phase != WavePhase::Intermission {
+        return;
+    }
+
+    director.intermission.tick(time.period);
+    if !director.intermission.finished() {
+        return;
+    }
+
+    let config = &config.waves;
+    director.wave += 1;
+    director.spec = config.wave(director.wave);
+    director.remaining = director.spec.enemies;
+    director.phase = WavePhase::Active;
+    director.intermission = Timer::from_seconds(config.intermission, TimerMode::Once);
+
+    wave_started_evw.send(WaveStarted(director.wave));
+}
+
+/// End the wave once every enemy has spawned and died
+pub fn clear_wave(
+    mut director: ResMut<WaveDirector>,
+    mut wave_cleared_evw: EventWriter<WaveCleared>,
+    enemy_q: Query<(), With<Enemy>>,
+) {
+    if director.phase != WavePhase::Active || director.remaining > 0 || !enemy_q.is_empty() {
+        return;
+    }
+
+    director.phase = WavePhase::Intermission;
+    director.intermission.reset();
+
+    wave_cleared_evw.send(WaveCleared(director.wave));
+}
@@ -0,0 +1,28 @@
+use bevy::prelude::*;
+
+pub mod weapons_cmps;
+mod weapons_sys;
+
+use weapons_sys::*;
+
+use crate::game::{config::config_res::GameConfig, GameplaySet};
+
+/// volume of weapon fire sounds
+pub const WEAPON_VOLUME: f32 = 0.5;
+
+pub struct WeaponsPlugin;
+
+impl Plugin for WeaponsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            (
+                tick_weapons,
+                switch_weapon,
+                reload_weapon,
+                apply_weapon_config.run_if(resource_exists_and_changed::<GameConfig>()),
+            )
+                .in_set(GameplaySet),
+        );
+    }
+}
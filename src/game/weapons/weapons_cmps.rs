@@ -0,0 +1,210 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::game::config::config_res::GameConfig;
+
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FireMode {
+    /// one shot per trigger pull
+    Semi,
+    /// keeps firing while the trigger is held
+    Auto,
+    /// fires the given number of shots per trigger pull
+    Burst(u32),
+}
+
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum WeaponKind {
+    Pistol,
+    Shotgun,
+    Smg,
+    Rifle,
+}
+
+impl WeaponKind {
+    /// weapons the player starts every run with, in switching order
+    pub const STARTER: [WeaponKind; 4] = [
+        WeaponKind::Pistol,
+        WeaponKind::Shotgun,
+        WeaponKind::Smg,
+        WeaponKind::Rifle,
+    ];
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct WeaponStats {
+    pub name: String,
+    pub fire_mode: FireMode,
+    /// seconds between shots
+    pub fire_rate: f32,
+    /// rounds per magazine
+    pub magazine: u32,
+    /// rounds carried on top of the magazine, None for unlimited
+    pub reserve: Option<u32>,
+    /// seconds to swap magazines
+    pub reload_time: f32,
+    /// damage per projectile
+    pub damage: f32,
+    pub projectile_speed: f32,
+    /// cone angle in degrees projectiles are scattered over
+    pub spread: f32,
+    /// projectiles per shot
+    pub projectiles: u32,
+    /// asset path of the shot sound
+    pub sound: String,
+}
+
+pub struct Weapon {
+    pub kind: WeaponKind,
+    pub stats: WeaponStats,
+    /// rounds left in the magazine
+    pub magazine: u32,
+    /// rounds left in reserve, None for unlimited
+    pub reserve: Option<u32>,
+    pub cooldown: Timer,
+    /// running while a reload is in progress
+    pub reload: Option<Timer>,
+    /// shots still to fire in the current burst
+    pub burst: u32,
+}
+
+impl Weapon {
+    pub fn new(kind: WeaponKind, stats: &WeaponStats) -> Self {
+        // start ready to fire
+        let mut cooldown = Timer::from_seconds(stats.fire_rate, TimerMode::Once);
+        cooldown.tick(Duration::from_secs_f32(stats.fire_rate));
+
+        Self {
+            kind,
+            magazine: stats.magazine,
+            reserve: stats.reserve,
+            stats: stats.clone(),
+            cooldown,
+            reload: None,
+            burst: 0,
+        }
+    }
+
+    pub fn is_reloading(&self) -> bool {
+        self.reload.is_some()
+    }
+
+    pub fn can_fire(&self) -> bool {
+        !self.is_reloading() && self.cooldown.finished() && self.magazine > 0
+    }
+
+    /// use up a round and restart the fire rate cooldown
+    pub fn fire(&mut self) {
+        self.magazine -= 1;
+        self.cooldown.reset();
+    }
+
+    pub fn can_reload(&self) -> bool {
+        !self.is_reloading()
+            && self.magazine < self.stats.magazine
+            && self.reserve.is_none_or(|reserve| reserve > 0)
+    }
+
+    pub fn start_reload(&mut self) {
+        if self.can_reload() {
+            self.burst = 0;
+            self.reload = Some(Timer::from_seconds(self.stats.reload_time, TimerMode::Once));
+        }
+    }
+
+    /// add magazines worth of rounds to the reserve, up to the most it can carry
+    pub fn restock(&mut self, magazines: u32) {
+        if let (Some(reserve), Some(max)) = (self.reserve.as_mut(), self.stats.reserve) {
+            *reserve = (*reserve + magazines * self.stats.magazine).min(max);
+        }
+    }
+
+    /// swap in new stats, keeping the ammo the weapon already holds
+    pub fn set_stats(&mut self, stats: &WeaponStats) {
+        self.stats = stats.clone();
+        self.magazine = self.magazine.min(stats.magazine);
+        self.reserve = match (self.reserve, stats.reserve) {
+            (_, None) => None,
+            (Some(reserve), Some(_)) => Some(reserve),
+            (None, Some(max)) => Some(max),
+        };
+        self.cooldown
+            .set_duration(Duration::from_secs_f32(stats.fire_rate));
+    }
+
+    pub fn cancel_reload(&mut self) {
+        self.reload = None;
+    }
+
+    /// `fire_rate` scales how fast the cooldown runs down
+    pub fn tick(&mut self, delta: Duration, fire_rate: f32) {
+        self.cooldown.tick(delta.mul_f32(fire_rate));
+
+        let Some(reload) = self.reload.as_mut() else { return };
+        if !reload.tick(delta).finished() {
+            return;
+        }
+
+        // move rounds from the reserve into the magazine
+        let missing = self.stats.magazine - self.magazine;
+        let loaded = match self.reserve.as_mut() {
+            Some(reserve) => {
+                let loaded = missing.min(*reserve);
+                *reserve -= loaded;
+                loaded
+            }
+            None => missing,
+        };
+
+        self.magazine += loaded;
+        self.reload = None;
+    }
+}
+
+/// every weapon a player carries and which one is in hand
+#[derive(Component)]
+pub struct Arsenal {
+    pub weapons: Vec<Weapon>,
+    pub equipped: usize,
+}
+
+impl Arsenal {
+    pub fn new(config: &GameConfig) -> Self {
+        Self {
+            weapons: WeaponKind::STARTER
+                .into_iter()
+                .map(|kind| Weapon::new(kind, config.weapon(kind)))
+                .collect(),
+            equipped: 0,
+        }
+    }
+
+    pub fn current(&self) -> &Weapon {
+        &self.weapons[self.equipped]
+    }
+
+    pub fn current_mut(&mut self) -> &mut Weapon {
+        &mut self.weapons[self.equipped]
+    }
+
+    /// switch to the weapon at the given slot, dropping any reload in progress
+    pub fn equip(&mut self, slot: usize) {
+        if slot >= self.weapons.len() || slot == self.equipped {
+            return;
+        }
+
+        let current = self.current_mut();
+        current.cancel_reload();
+        current.burst = 0;
+        self.equipped = slot;
+    }
+
+    /// cycle through weapons, wrapping around at either end
+    pub fn cycle(&mut self, step: i32) {
+        let len = self.weapons.len() as i32;
+        let slot = (self.equipped as i32 + step).rem_euclid(len);
+        self.equip(slot as usize);
+    }
+}
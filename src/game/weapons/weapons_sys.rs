@@ -0,0 +1,76 @@
+use bevy::prelude::*;
+
+use crate::{
+    actions::actions_res::Action,
+    game::{
+        config::config_res::GameConfig,
+        player::player_cmps::{LivingPlayer, Player, PlayerInput},
+        stats::stats_cmps::FireRate,
+    },
+};
+
+use super::weapons_cmps::Arsenal;
+
+const SLOT_ACTIONS: [Action; 4] = [
+    Action::Weapon1,
+    Action::Weapon2,
+    Action::Weapon3,
+    Action::Weapon4,
+];
+
+/// Fire rate effects speed up the cooldown between shots, reloads take as long as ever
+pub fn tick_weapons(
+    time: Res<FixedTime>,
+    mut arsenal_q: Query<(&mut Arsenal, &FireRate), With<Player>>,
+) {
+    for (mut arsenal, fire_rate) in arsenal_q.iter_mut() {
+        for weapon in arsenal.weapons.iter_mut() {
+            weapon.tick(time.period, fire_rate.0.value);
+        }
+    }
+}
+
+/// Weapon slot actions pick a slot directly, next & previous cycle through slots
+pub fn switch_weapon(mut arsenal_q: Query<(&mut Arsenal, &PlayerInput), LivingPlayer>) {
+    for (mut arsenal, input) in arsenal_q.iter_mut() {
+        let actions = &input.actions;
+
+        for (slot, action) in SLOT_ACTIONS.iter().enumerate() {
+            if actions.just_pressed(*action) {
+                arsenal.equip(slot);
+            }
+        }
+
+        if actions.just_pressed(Action::PrevWeapon) {
+            arsenal.cycle(-1);
+        }
+
+        if actions.just_pressed(Action::NextWeapon) {
+            arsenal.cycle(1);
+        }
+    }
+}
+
+/// Reload on the reload action, or automatically once the magazine runs dry
+pub fn reload_weapon(mut arsenal_q: Query<(&mut Arsenal, &PlayerInput), With<Player>>) {
+    for (mut arsenal, input) in arsenal_q.iter_mut() {
+        let actions = &input.actions;
+        let weapon = arsenal.current_mut();
+
+        if actions.just_pressed(Action::Reload) || weapon.magazine == 0 {
+            weapon.start_reload();
+        }
+    }
+}
+
+/// Pick up weapon stat changes from a reloaded gameplay config
+pub fn apply_weapon_config(
+    config: Res<GameConfig>,
+    mut arsenal_q: Query<&mut Arsenal, With<Player>>,
+) {
+    for mut arsenal in arsenal_q.iter_mut() {
+        for weapon in arsenal.weapons.iter_mut() {
+            weapon.set_stats(config.weapon(weapon.kind));
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use bevy_common_assets::ron::RonAssetPlugin;
 
 pub mod world_cmps;
 pub mod world_res;
@@ -7,27 +8,33 @@ mod world_sys;
 use world_res::*;
 use world_sys::*;
 
-use crate::AppState;
+use crate::{game::GameplaySet, AppState};
 
-pub const MAP_SIZE: f32 = 25.0;
-pub const WALL_HEIGHT: f32 = 2.0;
+/// every `.level.ron` file in here is a selectable arena
+pub const LEVELS_DIR: &str = "levels";
+pub const LIGHT_CHANGE_BEATS: u32 = 1;
 
 pub struct WorldPlugin;
 
 impl Plugin for WorldPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<LightTimer>()
-            .init_resource::<Colors>()
+        app.add_plugins(RonAssetPlugin::<Level>::new(&["level.ron"]))
+            .init_resource::<Levels>()
+            .init_resource::<SelectedLevel>()
+            .add_systems(Startup, load_levels)
+            .add_systems(Update, sync_levels)
+            // after everything that picks a level, so the next state already has it
+            // held back during a run so edits apply from the next one on
+            .add_systems(
+                PostUpdate,
+                apply_selected_level.run_if(not(in_state(AppState::Game)).and_then(
+                    resource_changed::<Levels>().or_else(resource_changed::<SelectedLevel>()),
+                )),
+            )
             .add_systems(
                 OnEnter(AppState::Game),
-                (
-                    spawn_floor,
-                    spawn_disco_light,
-                    spawn_walls,
-                    spawn_tables,
-                    spawn_bar_table,
-                ),
+                (spawn_floor, spawn_walls, spawn_props, spawn_lights),
             )
-            .add_systems(Update, (change_light_clr,).run_if(in_state(AppState::Game)));
+            .add_systems(Update, change_light_clr.in_set(GameplaySet));
     }
 }
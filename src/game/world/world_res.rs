@@ -1,32 +1,192 @@
-use bevy::prelude::*;
+use bevy::{
+    prelude::*,
+    reflect::{TypePath, TypeUuid},
+};
+use rand::Rng;
+use serde::Deserialize;
 
-use crate::game;
+/// Arena loaded from a `.level.ron` file in the levels folder
+/// The selected one is inserted as a resource, the next run is spawned from it
+#[derive(Resource, Deserialize, TypeUuid, TypePath, Clone)]
+#[uuid = "ce07b943-ee5d-4fb8-b936-e9b70c5b99e4"]
+pub struct Level {
+    /// shown in the map picker, high scores & replays are kept by it
+    pub name: String,
+    /// image shown in the map picker
+    pub preview: String,
+    /// length of the sides of the square arena, centered on the origin
+    pub size: f32,
+    pub floor: Floor,
+    pub wall_height: f32,
+    pub wall_color: Color,
+    #[serde(default)]
+    pub wall_texture: Option<String>,
+    pub walls: Vec<Wall>,
+    pub props: Vec<Prop>,
+    /// position of the light that changes color with the music
+    pub disco_light: Vec3,
+    pub lights: Vec<LevelLight>,
+    /// colors the disco light changes between
+    pub palette: Vec<Color>,
+    /// music tracks without their extension, every run picks one of them
+    pub playlist: Vec<String>,
+    pub enemy_spawns: Vec<SpawnZone>,
+    pub powerup_spawns: Vec<SpawnZone>,
+}
+
+impl Level {
+    pub fn half_size(&self) -> f32 {
+        self.size / 2.0
+    }
+
+    /// Whether a position is within the arena walls
+    pub fn contains(&self, pos: Vec3) -> bool {
+        pos.x.abs() <= self.half_size() && pos.z.abs() <= self.half_size()
+    }
+
+    /// Random point on the floor in one of the zones, bigger zones are picked more often
+    pub fn random_point(zones: &[SpawnZone], rng: &mut impl Rng) -> Vec3 {
+        let total: f32 = zones.iter().map(SpawnZone::area).sum();
+        let mut pick = rng.gen_range(0.0..total);
+
+        let zone = zones
+            .iter()
+            .find(|zone| {
+                pick -= zone.area();
+                pick < 0.0
+            })
+            .unwrap_or(&zones[zones.len() - 1]);
+
+        Vec3::new(
+            rng.gen_range(zone.min.x..=zone.max.x),
+            0.0,
+            rng.gen_range(zone.min.y..=zone.max.y),
+        )
+    }
+
+    /// Make sure the arena has room to spawn enemies and powerups in, colors & music to play
+    pub fn validate(&self) -> Result<(), String> {
+        if self.size <= 0.0 {
+            return Err("size must be greater than 0".to_string());
+        }
+
+        if self.palette.is_empty() {
+            return Err("palette needs at least one color".to_string());
+        }
+
+        if self.playlist.is_empty() {
+            return Err("playlist needs at least one track".to_string());
+        }
+
+        for (name, zones) in [
+            ("enemy_spawns", &self.enemy_spawns),
+            ("powerup_spawns", &self.powerup_spawns),
+        ] {
+            if zones.iter().any(|zone| zone.min.cmpgt(zone.max).any()) {
+                return Err(format!("{} has a zone with min above max", name));
+            }
+
+            if zones.iter().map(SpawnZone::area).sum::<f32>() <= 0.0 {
+                return Err(format!("{} needs at least one zone with an area", name));
+            }
+
+            let outside = zones.iter().any(|zone| {
+                let corner = zone.min.abs().max(zone.max.abs());
+                corner.max_element() > self.half_size()
+            });
+            if outside {
+                return Err(format!("{} must be inside the arena", name));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Deserialize, Clone)]
+pub enum Floor {
+    /// path of a model sized to the arena
+    Model(String),
+    /// flat floor covering the arena
+    Plane {
+        color: Color,
+        #[serde(default)]
+        texture: Option<String>,
+    },
+}
+
+/// Straight wall between two points on the floor
+/// Walls are drawn one-sided, facing right when looking from start to end,
+/// so the camera can see through them from behind
+#[derive(Deserialize, Clone)]
+pub struct Wall {
+    pub start: Vec2,
+    pub end: Vec2,
+    #[serde(default)]
+    pub two_sided: bool,
+}
+
+/// Model placed in the arena, blocking players, enemies and the nav grid with its collider
+#[derive(Deserialize, Clone)]
+pub struct Prop {
+    pub name: String,
+    pub model: String,
+    pub position: Vec3,
+    /// degrees around the vertical axis
+    #[serde(default)]
+    pub rotation: f32,
+    pub collider: PropCollider,
+}
 
-#[derive(Resource)]
-pub struct LightTimer(pub Timer);
+#[derive(Deserialize, Clone)]
+pub enum PropCollider {
+    Cuboid { half_extents: Vec3 },
+    Cylinder { half_height: f32, radius: f32 },
+    None,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct LevelLight {
+    pub position: Vec3,
+    pub color: Color,
+    pub intensity: f32,
+    #[serde(default)]
+    pub shadows: bool,
+}
+
+/// Rectangle on the floor, x & y of the corners are world x & z
+#[derive(Deserialize, Clone)]
+pub struct SpawnZone {
+    pub min: Vec2,
+    pub max: Vec2,
+}
 
-impl Default for LightTimer {
-    fn default() -> Self {
-        LightTimer(Timer::from_seconds(1.0, TimerMode::Repeating))
+impl SpawnZone {
+    pub fn area(&self) -> f32 {
+        let size = (self.max - self.min).max(Vec2::ZERO);
+        size.x * size.y
     }
 }
 
-#[derive(Resource)]
-pub struct Colors(pub Vec<Color>);
+/// Every level file in the levels folder
+#[derive(Resource, Default)]
+pub struct LevelHandles(pub Vec<Handle<Level>>);
+
+/// Every valid level, sorted by name
+#[derive(Resource, Default)]
+pub struct Levels(pub Vec<Level>);
 
-impl Default for Colors {
-    fn default() -> Self {
-        let mut colors = Vec::new();
-        colors.push(Color::BLUE);
-        colors.push(Color::RED);
-        colors.push(Color::YELLOW);
-        colors.push(Color::PURPLE);
-        colors.push(Color::PINK);
-        colors.push(Color::ORANGE);
-        colors.push(Color::TEAL);
-        colors.push(Color::AZURE);
-        colors.push(Color::VIOLET);
+impl Levels {
+    pub fn find(&self, name: &str) -> Option<&Level> {
+        self.0.iter().find(|level| level.name == name)
+    }
 
-        game::world::world_res::Colors(colors)
+    /// The level named name, the first one if there is none by that name (anymore)
+    pub fn find_or_first(&self, name: &str) -> Option<&Level> {
+        self.find(name).or(self.0.first())
     }
 }
+
+/// Name of the level the next run is played on, kept by name so reloads can't move it
+#[derive(Resource, Default)]
+pub struct SelectedLevel(pub String);
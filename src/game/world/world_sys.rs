@@ -1,69 +1,191 @@
 use bevy::{prelude::*, render::render_resource::Face};
 use bevy_rapier3d::prelude::*;
 use rand::Rng;
-use std::f32::consts::PI;
 
-use crate::game::game_cmps::Game;
-
-use super::{
-    world_cmps::DiscoLight,
-    world_res::{Colors, LightTimer},
-    MAP_SIZE, WALL_HEIGHT,
+use crate::game::{
+    game_cmps::Game,
+    game_res::{GameRng, RngStream},
+    music::music_evs::Beat,
 };
 
-pub fn spawn_floor(mut cmds: Commands, assets: Res<AssetServer>) {
-    let floor = (
-        SceneBundle {
-            scene: assets.load("models/floor.gltf#Scene0"),
-            ..default()
-        },
-        Collider::cuboid(MAP_SIZE / 2.0, 0.0, MAP_SIZE / 2.0),
-        Game,
-        Name::new("Floor"),
-    );
+use super::{world_cmps::DiscoLight, world_res::*, *};
 
-    cmds.spawn(floor);
+pub fn load_levels(mut cmds: Commands, assets: Res<AssetServer>) {
+    let handles = match assets.load_folder(LEVELS_DIR) {
+        Ok(handles) => handles.into_iter().map(|handle| handle.typed()).collect(),
+        Err(e) => {
+            error!("Could not load the levels: {}", e);
+            Vec::new()
+        }
+    };
+
+    cmds.insert_resource(LevelHandles(handles));
+}
+
+/// Rebuild the list of levels whenever one of them is (re)loaded
+/// Invalid levels are left out so a broken edit can't be played
+pub fn sync_levels(
+    mut level_evr: EventReader<AssetEvent<Level>>,
+    assets: Res<Assets<Level>>,
+    handles: Res<LevelHandles>,
+    mut levels: ResMut<Levels>,
+) {
+    let changed = level_evr
+        .iter()
+        .any(|ev| matches!(ev, AssetEvent::Created { .. } | AssetEvent::Modified { .. }));
+
+    if !changed {
+        return;
+    }
+
+    let mut valid = Vec::new();
+    for level in handles.0.iter().filter_map(|handle| assets.get(handle)) {
+        match level.validate() {
+            Ok(()) => valid.push(level.clone()),
+            Err(e) => error!("Invalid level {}: {}", level.name, e),
+        }
+    }
+    valid.sort_by(|a, b| a.name.cmp(&b.name));
+
+    info!("Loaded {} levels", valid.len());
+    levels.0 = valid;
+}
+
+/// Insert the selected level as the one the next run is spawned from
+pub fn apply_selected_level(mut cmds: Commands, levels: Res<Levels>, selected: Res<SelectedLevel>) {
+    if let Some(level) = levels.find_or_first(&selected.0) {
+        cmds.insert_resource(level.clone());
+    }
+}
+
+pub fn spawn_floor(
+    mut cmds: Commands,
+    assets: Res<AssetServer>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    level: Res<Level>,
+) {
+    let collider = Collider::cuboid(level.half_size(), 0.0, level.half_size());
+
+    match &level.floor {
+        Floor::Model(model) => {
+            cmds.spawn((
+                SceneBundle {
+                    scene: assets.load(format!("{}#Scene0", model)),
+                    ..default()
+                },
+                collider,
+                Game,
+                Name::new("Floor"),
+            ));
+        }
+        Floor::Plane { color, texture } => {
+            cmds.spawn((
+                PbrBundle {
+                    mesh: meshes.add(Mesh::from(shape::Plane {
+                        size: level.size,
+                        ..default()
+                    })),
+                    material: materials.add(StandardMaterial {
+                        base_color: *color,
+                        base_color_texture: texture.as_ref().map(|path| assets.load(path)),
+                        perceptual_roughness: 0.9,
+                        ..default()
+                    }),
+                    ..default()
+                },
+                collider,
+                Game,
+                Name::new("Floor"),
+            ));
+        }
+    }
 }
 
 pub fn spawn_walls(
     mut cmds: Commands,
+    assets: Res<AssetServer>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
+    level: Res<Level>,
 ) {
-    let mut wall = |x_pos: f32, z_pos: f32, y_rotation: f32, face: Face, name: &str| {
-        (
+    let height = level.wall_height;
+
+    for wall in level.walls.iter() {
+        let length = wall.start.distance(wall.end);
+        let dir = (wall.end - wall.start).normalize_or_zero();
+        let center = (wall.start + wall.end) / 2.0;
+
+        let cull_mode = if wall.two_sided {
+            None
+        } else {
+            Some(Face::Back)
+        };
+
+        cmds.spawn((
             PbrBundle {
                 mesh: meshes.add(Mesh::from(shape::Quad {
-                    size: Vec2::new(MAP_SIZE, WALL_HEIGHT),
+                    size: Vec2::new(length, height),
                     ..default()
                 })),
-                material: materials
-                    .add(StandardMaterial {
-                        base_color: Color::CRIMSON.into(),
-                        cull_mode: Some(face),
-                        ..default()
-                    })
-                    .clone(),
+                material: materials.add(StandardMaterial {
+                    base_color: level.wall_color,
+                    base_color_texture: level.wall_texture.as_ref().map(|path| assets.load(path)),
+                    cull_mode,
+                    double_sided: wall.two_sided,
+                    ..default()
+                }),
                 transform: Transform {
-                    translation: Vec3::new(x_pos / 2.0, WALL_HEIGHT / 2.0, z_pos / 2.0),
-                    rotation: Quat::from_rotation_y(y_rotation),
+                    translation: Vec3::new(center.x, height / 2.0, center.y),
+                    // the quad faces +z, turn it to run from start to end facing right
+                    rotation: Quat::from_rotation_y((-dir.y).atan2(dir.x)),
                     ..default()
                 },
                 ..default()
             },
-            Collider::cuboid(MAP_SIZE / 2.0, WALL_HEIGHT / 2.0, 0.0),
+            Collider::cuboid(length / 2.0, height / 2.0, 0.0),
             Game,
-            Name::new(name.to_string()),
-        )
-    };
+            Name::new("Wall"),
+        ));
+    }
+}
 
-    cmds.spawn(wall(0.0, MAP_SIZE, 0.0, Face::Front, "North Wall"));
-    cmds.spawn(wall(0.0, -MAP_SIZE, 0.0, Face::Back, "South Wall"));
-    cmds.spawn(wall(MAP_SIZE, 0.0, PI / 2.0, Face::Front, "East Wall"));
-    cmds.spawn(wall(-MAP_SIZE, 0.0, PI / 2.0, Face::Back, "West Wall"));
+pub fn spawn_props(mut cmds: Commands, assets: Res<AssetServer>, level: Res<Level>) {
+    for prop in level.props.iter() {
+        let mut ent = cmds.spawn((
+            SceneBundle {
+                scene: assets.load(format!("{}#Scene0", prop.model)),
+                transform: Transform {
+                    translation: prop.position,
+                    rotation: Quat::from_rotation_y(prop.rotation.to_radians()),
+                    ..default()
+                },
+                ..default()
+            },
+            Game,
+            Name::new(prop.name.clone()),
+        ));
+
+        match prop.collider {
+            PropCollider::Cuboid { half_extents } => {
+                ent.insert(Collider::cuboid(
+                    half_extents.x,
+                    half_extents.y,
+                    half_extents.z,
+                ));
+            }
+            PropCollider::Cylinder {
+                half_height,
+                radius,
+            } => {
+                ent.insert(Collider::cylinder(half_height, radius));
+            }
+            PropCollider::None => (),
+        }
+    }
 }
 
-pub fn spawn_disco_light(mut cmds: Commands) {
+pub fn spawn_lights(mut cmds: Commands, level: Res<Level>) {
     cmds.spawn((
         PointLightBundle {
             point_light: PointLight {
@@ -71,116 +193,49 @@ pub fn spawn_disco_light(mut cmds: Commands) {
                 shadows_enabled: true,
                 ..default()
             },
-            transform: Transform::from_xyz(-5.0, 5.0, -4.5),
+            transform: Transform::from_translation(level.disco_light),
             ..default()
         },
         DiscoLight,
         Game,
-        Name::new("Point Light"),
+        Name::new("Disco Light"),
     ));
-}
-
-pub fn spawn_tables(mut cmds: Commands, assets: Res<AssetServer>) {
-    let table =
-        |x: f32, z: f32, asset_server: &AssetServer| -> (SceneBundle, Collider, Game, Name) {
-            (
-                SceneBundle {
-                    scene: asset_server.load("models/Table.gltf#Scene0"),
-                    transform: Transform {
-                        translation: Vec3::new(x, 0.35, z),
-                        ..default()
-                    },
-                    ..default()
-                },
-                Collider::cylinder(0.32, 0.7),
-                Game,
-                Name::new("Table"),
-            )
-        };
-
-    let chair = |x: f32, z: f32, asset_server: &AssetServer| -> (SceneBundle, Collider, Name) {
-        (
-            SceneBundle {
-                scene: asset_server.load("models/Chair.gltf#Scene0"),
-                transform: Transform {
-                    translation: Vec3::new(x, 0.0, z),
-                    ..default()
-                },
-                ..default()
-            },
-            Collider::cuboid(0.15, 0.15, 0.15),
-            Name::new("Chair"),
-        )
-    };
-
-    cmds.spawn(table(-0.7, 4.1, &assets))
-        .with_children(|parent| {
-            parent.spawn(chair(0.4, 0.0, &assets));
-            parent.spawn(chair(0.0, 0.4, &assets));
-        });
-    cmds.spawn(table(-2.7, 8.0, &assets))
-        .with_children(|parent| {
-            parent.spawn(chair(-0.4, 0.0, &assets));
-            parent.spawn(chair(0.0, 0.4, &assets));
-        });
-
-    cmds.spawn(table(-4.3, 4.6, &assets))
-        .with_children(|parent| {
-            parent.spawn(chair(0.4, 0.0, &assets));
-        });
-
-    cmds.spawn(table(-7.1, 7.7, &assets))
-        .with_children(|parent| {
-            parent.spawn(chair(0.4, 0.4, &assets));
-        });
-}
 
-pub fn spawn_bar_table(mut cmds: Commands, assets: Res<AssetServer>) {
-    let light = |pos: Vec3| -> (PointLightBundle, Name) {
-        (
+    for light in level.lights.iter() {
+        cmds.spawn((
             PointLightBundle {
                 point_light: PointLight {
-                    color: Color::YELLOW_GREEN,
-                    intensity: 250.0,
-                    shadows_enabled: true,
+                    color: light.color,
+                    intensity: light.intensity,
+                    shadows_enabled: light.shadows,
                     ..default()
                 },
-                transform: Transform::from_translation(pos),
+                transform: Transform::from_translation(light.position),
                 ..default()
             },
-            Name::new("Bar Table Light"),
-        )
-    };
-
-    let bar_table = (
-        SceneBundle {
-            scene: assets.load("models/BarTable.gltf#Scene0"),
-            transform: Transform::from_xyz(8.8, 0.28, 7.0),
-            ..default()
-        },
-        Collider::cuboid(0.5, 0.25, 3.1),
-        Game,
-        Name::new("Bar Table"),
-    );
-
-    cmds.spawn(bar_table).with_children(|parent| {
-        parent.spawn(light(Vec3::new(0.0, 3.2, 2.0)));
-        parent.spawn(light(Vec3::new(0.0, 3.2, 0.0)));
-        parent.spawn(light(Vec3::new(0.0, 3.2, -2.0)));
-    });
+            Game,
+            Name::new("Light"),
+        ));
+    }
 }
 
+/// Change the disco light to a color of the level's palette every LIGHT_CHANGE_BEATS beats
 pub fn change_light_clr(
     mut light_q: Query<&mut PointLight, With<DiscoLight>>,
-    mut light_timer: ResMut<LightTimer>,
-    time: Res<Time>,
-    colors: Res<Colors>,
+    mut beat_evr: EventReader<Beat>,
+    level: Res<Level>,
+    mut game_rng: ResMut<GameRng>,
 ) {
-    light_timer.0.tick(time.delta());
     if let Ok(mut light) = light_q.get_single_mut() {
-        if light_timer.0.finished() {
-            let rng = rand::thread_rng().gen_range(0..colors.0.len());
-            light.color = colors.0[rng].into();
+        for ev in beat_evr.iter() {
+            if !ev.0.is_multiple_of(LIGHT_CHANGE_BEATS) {
+                continue;
+            }
+
+            let idx = game_rng
+                .stream(RngStream::Effects)
+                .gen_range(0..level.palette.len());
+            light.color = level.palette[idx];
         }
     }
 }
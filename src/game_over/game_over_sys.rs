@@ -1,16 +1,68 @@
-use bevy::prelude::*;
-
-use crate::game::player::player_res::KillCount;
-use crate::{game::game_res::GameTime, gamepad::gamepad_rcs::MyGamepad, AppState};
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+use crate::game::player::player_res::{KillCount, RhythmAccuracy};
+use crate::game::replay::replay_res::Playback;
+use crate::game::world::world_res::Level;
+use crate::leaderboard::leaderboard_res::{HighScores, LastRun, RunRecord};
+use crate::{
+    actions::actions_res::{Action, InputActions},
+    game::game_res::{GameMode, GameRng, GameTime},
+    AppState,
+};
 
 use super::game_over_cmps::*;
 use super::*;
 
+/// Everything the high score table keeps of the finished run
+#[derive(SystemParam)]
+pub struct FinishedRun<'w> {
+    game_time: Res<'w, GameTime>,
+    kills: Res<'w, KillCount>,
+    mode: Res<'w, GameMode>,
+    game_rng: Res<'w, GameRng>,
+    level: Res<'w, Level>,
+}
+
+impl FinishedRun<'_> {
+    pub fn record(&self) -> RunRecord {
+        RunRecord {
+            kills: self.kills.0,
+            time: self.game_time.0.elapsed().as_secs_f32(),
+            date: chrono::Local::now().format("%Y-%m-%d %H:%M").to_string(),
+            mode: *self.mode,
+            seed: Some(self.game_rng.seed),
+            level: self.level.name.clone(),
+        }
+    }
+}
+
+/// Add the finished run to the high score table and save it
+pub fn record_run(
+    run: FinishedRun,
+    playback: Option<Res<Playback>>,
+    mut high_scores: ResMut<HighScores>,
+    mut last_run: ResMut<LastRun>,
+) {
+    // watching a replay is not a new run
+    if playback.is_some() {
+        last_run.rank = None;
+        return;
+    }
+
+    last_run.rank = high_scores.insert(run.record());
+    if last_run.rank.is_some() {
+        high_scores.save();
+    }
+}
+
 pub fn spawn_game_over_menu(
     mut cmds: Commands,
     assets: Res<AssetServer>,
     game_time: Res<GameTime>,
     kills: Res<KillCount>,
+    accuracy: Res<RhythmAccuracy>,
+    last_run: Res<LastRun>,
+    game_rng: Res<GameRng>,
 ) {
     let img_container = (
         ImageBundle {
@@ -42,6 +94,18 @@ pub fn spawn_game_over_menu(
         Name::new("Kill Count Text"),
     );
 
+    let on_beat_txt = (
+        TextBundle::from_section(
+            format!("On Beat: {:.0}%", accuracy.percent()),
+            TextStyle {
+                color: Color::WHITE,
+                font: assets.load("fonts/PermanentMarker-Regular.ttf"),
+                font_size: 40.0,
+            },
+        ),
+        Name::new("On Beat Text"),
+    );
+
     let time = game_time.0.elapsed().as_secs_f32();
     let time_survived_txt = (
         TextBundle::from_section(
@@ -56,6 +120,35 @@ pub fn spawn_game_over_menu(
         Name::new("Time Survived Text"),
     );
 
+    let seed_txt = (
+        TextBundle::from_section(
+            format!("Seed: {}", game_rng.seed),
+            TextStyle {
+                color: Color::WHITE,
+                font: assets.load("fonts/PermanentMarker-Regular.ttf"),
+                font_size: 30.0,
+            },
+        ),
+        Name::new("Seed Text"),
+    );
+
+    let rank_txt = match last_run.rank {
+        Some(1) => "New personal best!".to_string(),
+        Some(rank) => format!("Rank #{}", rank),
+        None => String::new(),
+    };
+    let rank_txt = (
+        TextBundle::from_section(
+            rank_txt,
+            TextStyle {
+                color: Color::GOLD,
+                font: assets.load("fonts/PermanentMarker-Regular.ttf"),
+                font_size: 50.0,
+            },
+        ),
+        Name::new("Rank Text"),
+    );
+
     let play_again_btn = (
         ButtonBundle {
             background_color: PLAY_AGAIN_BTN_COLOR.into(),
@@ -103,12 +196,21 @@ pub fn spawn_game_over_menu(
 
     // game over menu
     cmds.spawn(img_container).with_children(|parent| {
+        // personal best / rank txt
+        parent.spawn(rank_txt);
+
         // time survived txt
         parent.spawn(time_survived_txt);
 
         // kill count txt
         parent.spawn(killcount_txt);
 
+        // on-beat percentage txt
+        parent.spawn(on_beat_txt);
+
+        // seed txt, to replay the same run with --seed
+        parent.spawn(seed_txt);
+
         // play again btn
         parent.spawn(play_again_btn).with_children(|parent| {
             // play again txt and btn img
@@ -118,22 +220,12 @@ pub fn spawn_game_over_menu(
     });
 }
 
-pub fn select_play_again_gamepad(
-    btns: Res<Input<GamepadButton>>,
-    cur_app_state_res: Res<State<AppState>>,
+pub fn select_play_again_action(
+    actions: Res<InputActions>,
     mut next_app_state: ResMut<NextState<AppState>>,
-    my_gamepad: Option<Res<MyGamepad>>,
 ) {
-    let gamepad_input = my_gamepad
-        .map(|gp| btns.just_pressed(GamepadButton::new(gp.gamepad, GamepadButtonType::North)))
-        .unwrap_or(false);
-
-    let current_state = cur_app_state_res.get();
-
-    if gamepad_input {
-        if current_state != &AppState::Game {
-            next_app_state.set(AppState::Game);
-        }
+    if actions.just_pressed(Action::Alternate) {
+        next_app_state.set(AppState::Game);
     }
 }
 
@@ -14,12 +14,15 @@ pub struct GameOverPlugin;
 
 impl Plugin for GameOverPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(AppState::GameOver), spawn_game_over_menu)
-            .add_systems(
-                Update,
-                (select_play_again_gamepad, select_play_again_mouse)
-                    .run_if(in_state(AppState::GameOver)),
-            )
-            .add_systems(OnExit(AppState::GameOver), despawn_game_over_menu);
+        app.add_systems(
+            OnEnter(AppState::GameOver),
+            (record_run, spawn_game_over_menu).chain(),
+        )
+        .add_systems(
+            Update,
+            (select_play_again_action, select_play_again_mouse)
+                .run_if(in_state(AppState::GameOver)),
+        )
+        .add_systems(OnExit(AppState::GameOver), despawn_game_over_menu);
     }
 }
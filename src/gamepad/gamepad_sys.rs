@@ -1,5 +1,5 @@
 use bevy::{
-    input::gamepad::{GamepadConnection::*, GamepadConnectionEvent},
+    input::gamepad::{GamepadConnection::*, GamepadConnectionEvent, Gamepads},
     prelude::*,
 };
 
@@ -8,20 +8,33 @@ use super::gamepad_rcs::*;
 pub fn connections(
     mut cmds: Commands,
     my_gamepad: Option<Res<MyGamepad>>,
+    gamepads: Res<Gamepads>,
     mut gamepad_evr: EventReader<GamepadConnectionEvent>,
 ) {
+    let mut current = my_gamepad.map(|gp| gp.gamepad);
+
     for ev in gamepad_evr.iter() {
         match &ev.connection {
             Connected(_info) => {
                 // if no gamepad is setup yet, use this one
-                if my_gamepad.is_none() {
-                    cmds.insert_resource(MyGamepad::default());
+                if current.is_none() {
+                    current = Some(ev.gamepad);
+                    cmds.insert_resource(MyGamepad {
+                        gamepad: ev.gamepad,
+                    });
                 }
-                // println!("Gamepad connected. ID: {}, name: {}", gp.id, _info.name);
             }
             Disconnected => {
-                cmds.remove_resource::<MyGamepad>();
-                // println!("Gamepad disconnected: ID: {}", gp.id);
+                if current != Some(ev.gamepad) {
+                    continue;
+                }
+
+                // fall back to any other gamepad that is still connected
+                current = gamepads.iter().find(|gp| *gp != ev.gamepad);
+                match current {
+                    Some(gamepad) => cmds.insert_resource(MyGamepad { gamepad }),
+                    None => cmds.remove_resource::<MyGamepad>(),
+                }
             }
         }
     }
@@ -5,6 +5,9 @@ mod gamepad_sys;
 
 use gamepad_sys::*;
 
+/// camera orbit speed at 1x gamepad sensitivity
+pub const GAMEPAD_SENSITIVITY: (f32, f32) = (7.0, 4.0);
+
 pub struct GamepadPlugin;
 
 impl Plugin for GamepadPlugin {
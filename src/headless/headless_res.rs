@@ -0,0 +1,90 @@
+use crate::game::game_res::GameRng;
+
+use super::{DEFAULT_MAX_TIME, DEFAULT_RUNS};
+
+/// Options of a `--headless` session
+pub struct HeadlessArgs {
+    pub runs: u32,
+    /// seed of the first run, every following run adds one to it
+    pub seed: u64,
+    /// runs that survive this long in seconds are cut off
+    pub max_time: f32,
+    /// name of the level every run is played on, the first one by name when missing
+    pub level: Option<String>,
+}
+
+impl HeadlessArgs {
+    /// Options missing from the args fall back to their defaults, invalid ones are an error
+    pub fn parse(args: &[String]) -> Result<Self, String> {
+        let value = |name: &str| -> Result<Option<&String>, String> {
+            match args.iter().position(|arg| arg == name) {
+                Some(idx) => args
+                    .get(idx + 1)
+                    .map(Some)
+                    .ok_or(format!("{} needs a value", name)),
+                None => Ok(None),
+            }
+        };
+
+        let runs = match value("--runs")? {
+            Some(runs) => runs
+                .parse::<u32>()
+                .ok()
+                .filter(|runs| *runs > 0)
+                .ok_or(format!("--runs needs a whole number above 0, got {}", runs))?,
+            None => DEFAULT_RUNS,
+        };
+
+        let seed = match value("--seed")? {
+            Some(seed) => GameRng::parse_seed(seed)?,
+            None => rand::random(),
+        };
+
+        let max_time = match value("--max-time")? {
+            Some(time) => time
+                .parse::<f32>()
+                .ok()
+                .filter(|time| time.is_finite() && *time > 0.0)
+                .ok_or(format!("--max-time needs seconds above 0, got {}", time))?,
+            None => DEFAULT_MAX_TIME,
+        };
+
+        Ok(Self {
+            runs,
+            seed,
+            max_time,
+            level: value("--level")?.cloned(),
+        })
+    }
+}
+
+/// Outcome of a single headless run, printed as one CSV row
+pub struct RunMetrics {
+    pub seed: u64,
+    pub level: String,
+    pub survival_time: f32,
+    pub kills: u32,
+    pub damage_taken: f32,
+    pub powerups: u32,
+    pub wave: u32,
+    pub timed_out: bool,
+}
+
+impl RunMetrics {
+    pub const CSV_HEADER: &'static str =
+        "seed,level,survival_time,kills,damage_taken,powerups,wave,timed_out";
+
+    pub fn csv_row(&self) -> String {
+        format!(
+            "{},{},{:.2},{},{:.1},{},{},{}",
+            self.seed,
+            self.level,
+            self.survival_time,
+            self.kills,
+            self.damage_taken,
+            self.powerups,
+            self.wave,
+            self.timed_out
+        )
+    }
+}
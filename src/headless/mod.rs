@@ -0,0 +1,211 @@
+use std::time::Duration;
+
+use bevy::{
+    asset::LoadState, input::InputPlugin, prelude::*, scene::ScenePlugin, time::TimeUpdateStrategy,
+    window::ExitCondition,
+};
+
+mod headless_res;
+
+use headless_res::*;
+
+use crate::{
+    actions::ActionsPlugin,
+    debug::debug_res::EnableDebugMode,
+    game::{
+        bot::bot_res::BotPlayers,
+        config::config_res::{GameConfig, GameConfigHandle},
+        game_res::{GameRng, GameTime, Headless},
+        music::music_res::TrackInfos,
+        player::player_res::{KillCount, RunStats},
+        waves::waves_res::WaveDirector,
+        world::world_res::{Level, LevelHandles, Levels, SelectedLevel},
+        GamePlugin, TICK_RATE,
+    },
+    settings::settings_res::Settings,
+    AppState, PauseState, SettingsState,
+};
+
+pub const DEFAULT_RUNS: u32 = 10;
+/// 10 minutes of game time
+pub const DEFAULT_MAX_TIME: f32 = 600.0;
+
+/// Simulate `--runs` games played by bots as fast as possible and print their metrics as CSV
+pub fn run(args: &[String]) {
+    let args = match HeadlessArgs::parse(args) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
+
+    println!("{}", RunMetrics::CSV_HEADER);
+
+    // a fresh app for every run, so each row can be reproduced on its own from its seed
+    for run in 0..args.runs {
+        let seed = args.seed.wrapping_add(run as u64);
+        let mut app = build_app(seed, args.level.as_deref());
+        let metrics = simulate(&mut app, seed, args.max_time);
+        println!("{}", metrics.csv_row());
+    }
+}
+
+fn build_app(seed: u64, level: Option<&str>) -> App {
+    let mut app = App::new();
+
+    app.insert_resource(Headless)
+        .insert_resource(BotPlayers)
+        .insert_resource(EnableDebugMode::new(false))
+        .insert_resource(Settings::default())
+        .insert_resource(GameRng::new(Some(seed)))
+        // every update is exactly one gameplay tick, no matter how long it took
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(
+            1.0 / TICK_RATE,
+        )))
+        .add_state::<AppState>()
+        .add_state::<PauseState>()
+        .add_state::<SettingsState>()
+        .add_plugins((
+            MinimalPlugins,
+            TransformPlugin,
+            HierarchyPlugin,
+            InputPlugin,
+            WindowPlugin {
+                primary_window: None,
+                exit_condition: ExitCondition::DontExit,
+                close_when_requested: false,
+            },
+            AssetPlugin::default(),
+            ScenePlugin,
+        ))
+        .add_asset::<Mesh>()
+        .add_asset::<StandardMaterial>()
+        .add_plugins((ActionsPlugin, GamePlugin));
+
+    app.finish();
+    app.cleanup();
+
+    // gameplay config, levels & tempos are loaded from files like in the game
+    loop {
+        match load_state(&app) {
+            LoadState::Loaded => break,
+            LoadState::Failed => {
+                eprintln!("Could not load the gameplay config, the levels or the music tempos");
+                std::process::exit(1);
+            }
+            _ => app.update(),
+        }
+    }
+
+    if let Some(name) = level {
+        if app.world.resource::<Levels>().find(name).is_none() {
+            eprintln!("There is no level named {}", name);
+            std::process::exit(1);
+        }
+        app.world.resource_mut::<SelectedLevel>().0 = name.to_string();
+    }
+    // copy them into their resources
+    app.update();
+
+    if !app.world.contains_resource::<GameConfig>() || !app.world.contains_resource::<Level>() {
+        eprintln!("The gameplay config or the level is invalid");
+        std::process::exit(1);
+    }
+
+    app
+}
+
+fn load_state(app: &App) -> LoadState {
+    let assets = app.world.resource::<AssetServer>();
+    let levels = app.world.resource::<Levels>();
+    let track_infos = app.world.resource::<TrackInfos>();
+
+    // the tempos are only requested once the levels listing their tracks are loaded
+    if levels.0.is_empty() || !track_infos.covers(levels) {
+        if let Some(handles) = app.world.get_resource::<LevelHandles>() {
+            let state = assets.get_group_load_state(handles.0.iter().map(|h| h.id()));
+            if state == LoadState::Failed || (state == LoadState::Loaded && handles.0.is_empty()) {
+                return LoadState::Failed;
+            }
+        }
+        return LoadState::NotLoaded;
+    }
+
+    match app.world.get_resource::<GameConfigHandle>() {
+        Some(config) => assets.get_group_load_state(
+            std::iter::once(config.0.id()).chain(track_infos.0.values().map(|h| h.id())),
+        ),
+        None => LoadState::NotLoaded,
+    }
+}
+
+/// Play a single run from the seed until game over or until it runs out of time
+fn simulate(app: &mut App, seed: u64, max_time: f32) -> RunMetrics {
+    app.world
+        .resource_mut::<NextState<AppState>>()
+        .set(AppState::Game);
+
+    let in_state = |app: &App, state: AppState| *app.world.resource::<State<AppState>>() == state;
+    let game_time = |app: &App| app.world.resource::<GameTime>().0.elapsed_secs();
+
+    app.update();
+
+    let mut timed_out = false;
+    while in_state(app, AppState::Game) {
+        if game_time(app) >= max_time {
+            timed_out = true;
+            app.world
+                .resource_mut::<NextState<AppState>>()
+                .set(AppState::GameOver);
+        }
+
+        app.update();
+    }
+
+    let stats = app.world.resource::<RunStats>();
+
+    RunMetrics {
+        seed,
+        level: app.world.resource::<Level>().name.clone(),
+        survival_time: game_time(app),
+        kills: app.world.resource::<KillCount>().0,
+        damage_taken: stats.damage_taken,
+        powerups: stats.powerups,
+        wave: app.world.resource::<WaveDirector>().wave,
+        timed_out,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::replay::replay_res::{GameTick, Playback, Recording};
+
+    const SEED: u64 = 7;
+    const MAX_TIME: f32 = 30.0;
+
+    /// A bot run played back from its recorded input ends on the same tick with the same kills
+    #[test]
+    fn replay_ends_with_recorded_kill_count() {
+        let mut recorded = build_app(SEED, None);
+        let metrics = simulate(&mut recorded, SEED, MAX_TIME);
+
+        let mut replay = recorded.world.resource::<Recording>().replay.clone();
+        replay.ticks = recorded.world.resource::<GameTick>().0;
+        replay.kills = metrics.kills;
+        assert!(replay.kills > 0, "the recorded run should kill something");
+
+        // the players follow the replay instead of the bots
+        let mut played = build_app(SEED, None);
+        played.world.remove_resource::<BotPlayers>();
+        played.insert_resource(Playback {
+            started: true,
+            ..Playback::new(replay.clone())
+        });
+        simulate(&mut played, SEED, MAX_TIME);
+
+        assert_eq!(played.world.resource::<KillCount>().0, replay.kills);
+        assert_eq!(played.world.resource::<GameTick>().0, replay.ticks);
+    }
+}
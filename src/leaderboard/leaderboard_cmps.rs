@@ -0,0 +1,7 @@
+use bevy::prelude::*;
+
+#[derive(Component)]
+pub struct Leaderboard;
+
+#[derive(Component)]
+pub struct BackBtn;
@@ -0,0 +1,100 @@
+use std::path::PathBuf;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    game::game_res::GameMode,
+    storage::{load_ron, project_dirs, save_ron},
+};
+
+use super::{HIGH_SCORES_FILE, LEGACY_LEVEL, MAX_HIGH_SCORES};
+
+/// A finished run as stored in the high score table
+#[derive(Serialize, Deserialize, Clone)]
+pub struct RunRecord {
+    pub kills: u32,
+    /// seconds survived
+    pub time: f32,
+    pub date: String,
+    pub mode: GameMode,
+    pub seed: Option<u64>,
+    /// name of the level, runs saved before there were several levels were all played in the club
+    #[serde(default = "legacy_level")]
+    pub level: String,
+}
+
+fn legacy_level() -> String {
+    LEGACY_LEVEL.to_string()
+}
+
+/// Best runs of every level sorted by kills, then by time survived
+#[derive(Resource, Serialize, Deserialize, Default)]
+pub struct HighScores {
+    pub runs: Vec<RunRecord>,
+}
+
+impl HighScores {
+    /// Read the table from the save file, starting empty if there is none
+    pub fn load() -> Self {
+        save_path()
+            .and_then(|path| load_ron(&path))
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        if let Some(path) = save_path() {
+            save_ron(&path, self);
+        }
+    }
+
+    /// Best runs played on the given level
+    pub fn for_level<'a>(&'a self, level: &'a str) -> impl Iterator<Item = &'a RunRecord> {
+        self.runs.iter().filter(move |run| run.level == level)
+    }
+
+    /// Add a run to its level's table, returning its rank (starting at 1) if it made the cut
+    pub fn insert(&mut self, run: RunRecord) -> Option<usize> {
+        let idx = self
+            .runs
+            .iter()
+            .position(|other| {
+                run.kills > other.kills || (run.kills == other.kills && run.time > other.time)
+            })
+            .unwrap_or(self.runs.len());
+
+        let rank = self.runs[..idx]
+            .iter()
+            .filter(|other| other.level == run.level)
+            .count();
+
+        if rank >= MAX_HIGH_SCORES {
+            return None;
+        }
+
+        let level = run.level.clone();
+        self.runs.insert(idx, run);
+
+        // drop the level's run that got pushed out of its table
+        let mut kept = 0;
+        self.runs.retain(|other| {
+            if other.level != level {
+                return true;
+            }
+            kept += 1;
+            kept <= MAX_HIGH_SCORES
+        });
+
+        Some(rank + 1)
+    }
+}
+
+/// Where the last finished run placed in the high score table
+#[derive(Resource, Default)]
+pub struct LastRun {
+    pub rank: Option<usize>,
+}
+
+fn save_path() -> Option<PathBuf> {
+    project_dirs().map(|dirs| dirs.data_dir().join(HIGH_SCORES_FILE))
+}
@@ -0,0 +1,156 @@
+use bevy::prelude::*;
+
+use crate::{
+    actions::actions_res::{Action, InputActions},
+    game::world::world_res::Level,
+    AppState,
+};
+
+use super::{
+    leaderboard_cmps::*, leaderboard_res::HighScores, BACK_BTN_COLOR, BACK_BTN_COLOR_HOVER,
+};
+
+pub fn spawn_leaderboard(
+    mut cmds: Commands,
+    assets: Res<AssetServer>,
+    high_scores: Res<HighScores>,
+    level: Option<Res<Level>>,
+) {
+    let font = assets.load("fonts/PermanentMarker-Regular.ttf");
+
+    let img_container = (
+        ImageBundle {
+            image: assets.load("imgs/main_menu_background.png").into(),
+            style: Style {
+                align_items: AlignItems::Center,
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                ..default()
+            },
+            ..default()
+        },
+        Leaderboard,
+        Name::new("Leaderboard Image"),
+    );
+
+    let title_txt = (
+        TextBundle::from_section(
+            match &level {
+                Some(level) => format!("Leaderboard - {}", level.name),
+                None => "Leaderboard".to_string(),
+            },
+            TextStyle {
+                color: Color::RED,
+                font: font.clone(),
+                font_size: 80.0,
+            },
+        )
+        .with_style(Style {
+            margin: UiRect::bottom(Val::Px(30.0)),
+            ..default()
+        }),
+        Name::new("Leaderboard Text"),
+    );
+
+    let row_style = TextStyle {
+        color: Color::WHITE,
+        font: font.clone(),
+        font_size: 30.0,
+    };
+
+    let level_name = level.as_ref().map_or("", |level| level.name.as_str());
+    let mut rows: Vec<String> = high_scores
+        .for_level(level_name)
+        .enumerate()
+        .map(|(idx, run)| {
+            let seed = run
+                .seed
+                .map(|s| format!(" - Seed {}", s))
+                .unwrap_or_default();
+            format!(
+                "{}. {} kills - {:.2}s - {} - {:?}{}",
+                idx + 1,
+                run.kills,
+                run.time,
+                run.date,
+                run.mode,
+                seed
+            )
+        })
+        .collect();
+
+    if rows.is_empty() {
+        rows.push("No runs yet".to_string());
+    }
+
+    let back_btn = (
+        ButtonBundle {
+            background_color: BACK_BTN_COLOR.into(),
+            style: Style {
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                width: Val::Px(150.0),
+                height: Val::Px(75.0),
+                margin: UiRect::top(Val::Px(50.0)),
+                ..default()
+            },
+            ..default()
+        },
+        BackBtn,
+        Name::new("Back Button"),
+    );
+
+    let back_txt = TextBundle::from_section(
+        "Back",
+        TextStyle {
+            color: Color::WHITE,
+            font,
+            font_size: 40.0,
+        },
+    );
+
+    cmds.spawn((Camera3dBundle::default(), Leaderboard));
+    cmds.spawn(img_container).with_children(|parent| {
+        parent.spawn(title_txt);
+
+        for row in rows {
+            parent.spawn(TextBundle::from_section(row, row_style.clone()));
+        }
+
+        parent.spawn(back_btn).with_children(|parent| {
+            parent.spawn(back_txt);
+        });
+    });
+}
+
+pub fn despawn_leaderboard(mut cmds: Commands, leaderboard_q: Query<Entity, With<Leaderboard>>) {
+    for ent in leaderboard_q.iter() {
+        cmds.entity(ent).despawn_recursive();
+    }
+}
+
+pub fn select_back_action(
+    actions: Res<InputActions>,
+    mut next_app_state: ResMut<NextState<AppState>>,
+) {
+    if actions.just_pressed(Action::Back) {
+        next_app_state.set(AppState::MainMenu);
+    }
+}
+
+type BackBtnInteraction = (Changed<Interaction>, With<BackBtn>);
+
+pub fn select_back_mouse(
+    mut interact_q: Query<(&Interaction, &mut BackgroundColor), BackBtnInteraction>,
+    mut next_app_state: ResMut<NextState<AppState>>,
+) {
+    for (interaction, mut background_clr) in &mut interact_q {
+        match *interaction {
+            Interaction::Pressed => next_app_state.set(AppState::MainMenu),
+            Interaction::Hovered => *background_clr = BACK_BTN_COLOR_HOVER.into(),
+            Interaction::None => *background_clr = BACK_BTN_COLOR.into(),
+        }
+    }
+}
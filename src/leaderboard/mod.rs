@@ -0,0 +1,33 @@
+use bevy::prelude::*;
+
+use crate::AppState;
+
+pub mod leaderboard_cmps;
+pub mod leaderboard_res;
+mod leaderboard_sys;
+
+use leaderboard_res::*;
+use leaderboard_sys::*;
+
+/// number of runs kept in the high score table of each level
+pub const MAX_HIGH_SCORES: usize = 10;
+/// level of the runs saved before high scores were kept per level
+pub const LEGACY_LEVEL: &str = "Club";
+pub const HIGH_SCORES_FILE: &str = "high_scores.ron";
+pub const BACK_BTN_COLOR: Color = Color::rgba(0.38, 0.0, 0.99, 0.9);
+pub const BACK_BTN_COLOR_HOVER: Color = Color::rgb(0.5, 0.0, 1.0);
+
+pub struct LeaderboardPlugin;
+
+impl Plugin for LeaderboardPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(HighScores::load())
+            .init_resource::<LastRun>()
+            .add_systems(OnEnter(AppState::Leaderboard), spawn_leaderboard)
+            .add_systems(OnExit(AppState::Leaderboard), despawn_leaderboard)
+            .add_systems(
+                Update,
+                (select_back_action, select_back_mouse).run_if(in_state(AppState::Leaderboard)),
+            );
+    }
+}
@@ -0,0 +1,14 @@
+use bevy::prelude::*;
+
+#[derive(Component)]
+pub struct LobbyMenu;
+
+/// One of the player slots, numbered from 0
+#[derive(Component)]
+pub struct LobbySlot(pub usize);
+
+#[derive(Component)]
+pub struct LobbySlotTxt(pub usize);
+
+#[derive(Component)]
+pub struct LobbyHintTxt;
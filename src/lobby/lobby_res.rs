@@ -0,0 +1,7 @@
+use bevy::prelude::*;
+
+use crate::actions::actions_res::InputSource;
+
+/// Devices that joined the co-op lobby, in player order
+#[derive(Resource, Default)]
+pub struct Lobby(pub Vec<InputSource>);
@@ -0,0 +1,182 @@
+use bevy::prelude::*;
+
+use crate::{
+    actions::actions_res::{Action, DeviceActions, InputSource},
+    game::{game_res::GameMode, player::player_res::Party},
+    AppState,
+};
+
+use super::{
+    lobby_cmps::*, lobby_res::Lobby, LOBBY_SLOT_COLOR, LOBBY_SLOT_COLOR_EMPTY, MAX_COOP_PLAYERS,
+    MIN_COOP_PLAYERS,
+};
+
+pub fn spawn_lobby(mut cmds: Commands, assets: Res<AssetServer>, mut lobby: ResMut<Lobby>) {
+    *lobby = Lobby::default();
+    let font = assets.load("fonts/PermanentMarker-Regular.ttf");
+
+    let img_container = (
+        ImageBundle {
+            image: assets.load("imgs/main_menu_background.png").into(),
+            style: Style {
+                align_items: AlignItems::Center,
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                ..default()
+            },
+            ..default()
+        },
+        LobbyMenu,
+        Name::new("Lobby Image"),
+    );
+
+    let title_txt = (
+        TextBundle::from_section(
+            "Co-op",
+            TextStyle {
+                color: Color::RED,
+                font: font.clone(),
+                font_size: 80.0,
+            },
+        )
+        .with_style(Style {
+            margin: UiRect::bottom(Val::Px(30.0)),
+            ..default()
+        }),
+        Name::new("Co-op Text"),
+    );
+
+    let txt_style = TextStyle {
+        color: Color::WHITE,
+        font,
+        font_size: 30.0,
+    };
+
+    let hint_txt = (
+        TextBundle::from_section("", txt_style.clone()).with_style(Style {
+            margin: UiRect::top(Val::Px(30.0)),
+            ..default()
+        }),
+        LobbyHintTxt,
+        Name::new("Lobby Hint Text"),
+    );
+
+    cmds.spawn((Camera3dBundle::default(), LobbyMenu));
+    cmds.spawn(img_container).with_children(|parent| {
+        parent.spawn(title_txt);
+
+        for idx in 0..MAX_COOP_PLAYERS {
+            let slot = (
+                NodeBundle {
+                    background_color: LOBBY_SLOT_COLOR_EMPTY.into(),
+                    style: Style {
+                        align_items: AlignItems::Center,
+                        justify_content: JustifyContent::Center,
+                        width: Val::Px(500.0),
+                        height: Val::Px(50.0),
+                        margin: UiRect::top(Val::Px(10.0)),
+                        ..default()
+                    },
+                    ..default()
+                },
+                LobbySlot(idx),
+                Name::new(format!("Player {} Slot", idx + 1)),
+            );
+
+            parent.spawn(slot).with_children(|parent| {
+                parent.spawn((
+                    TextBundle::from_section("", txt_style.clone()),
+                    LobbySlotTxt(idx),
+                ));
+            });
+        }
+
+        parent.spawn(hint_txt);
+    });
+}
+
+pub fn despawn_lobby(mut cmds: Commands, lobby_q: Query<Entity, With<LobbyMenu>>) {
+    for ent in lobby_q.iter() {
+        cmds.entity(ent).despawn_recursive();
+    }
+}
+
+/// Confirm on a device joins it, Back leaves again or returns to the main menu
+/// Player 1 starts the game with Confirm once enough players joined
+pub fn join_lobby(
+    device_actions: Res<DeviceActions>,
+    mut lobby: ResMut<Lobby>,
+    mut party: ResMut<Party>,
+    mut mode: ResMut<GameMode>,
+    mut next_app_state: ResMut<NextState<AppState>>,
+) {
+    // gamepads that got unplugged leave the lobby
+    lobby
+        .0
+        .retain(|source| device_actions.get(*source).is_some());
+
+    for (source, actions) in device_actions.iter() {
+        if *source == InputSource::Any {
+            continue;
+        }
+
+        let joined = lobby.0.iter().position(|other| other == source);
+
+        if actions.just_pressed(Action::Confirm) {
+            match joined {
+                Some(0) if lobby.0.len() >= MIN_COOP_PLAYERS => {
+                    party.0 = lobby.0.clone();
+                    *mode = GameMode::Coop;
+                    next_app_state.set(AppState::Game);
+                }
+                None if lobby.0.len() < MAX_COOP_PLAYERS => lobby.0.push(*source),
+                _ => (),
+            }
+        }
+
+        if actions.just_pressed(Action::Back) {
+            match joined {
+                Some(idx) => {
+                    lobby.0.remove(idx);
+                }
+                None => next_app_state.set(AppState::MainMenu),
+            }
+        }
+    }
+}
+
+pub fn update_lobby_slots(
+    lobby: Res<Lobby>,
+    mut slot_q: Query<(&LobbySlot, &mut BackgroundColor)>,
+    mut slot_txt_q: Query<(&LobbySlotTxt, &mut Text)>,
+    mut hint_txt_q: Query<&mut Text, (With<LobbyHintTxt>, Without<LobbySlotTxt>)>,
+) {
+    if !lobby.is_changed() {
+        return;
+    }
+
+    for (slot, mut background_clr) in slot_q.iter_mut() {
+        *background_clr = if slot.0 < lobby.0.len() {
+            LOBBY_SLOT_COLOR.into()
+        } else {
+            LOBBY_SLOT_COLOR_EMPTY.into()
+        };
+    }
+
+    for (slot, mut txt) in slot_txt_q.iter_mut() {
+        txt.sections[0].value = match lobby.0.get(slot.0) {
+            Some(source) => format!("Player {} - {}", slot.0 + 1, source.label()),
+            None => "Press Confirm to join".to_string(),
+        };
+    }
+
+    if let Ok(mut txt) = hint_txt_q.get_single_mut() {
+        txt.sections[0].value = if lobby.0.len() >= MIN_COOP_PLAYERS {
+            "Player 1 presses Confirm to start - Back to leave".to_string()
+        } else {
+            format!("Waiting for {} players - Back to leave", MIN_COOP_PLAYERS)
+        };
+    }
+}
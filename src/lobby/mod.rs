@@ -0,0 +1,31 @@
+use bevy::prelude::*;
+
+use crate::AppState;
+
+pub mod lobby_cmps;
+pub mod lobby_res;
+mod lobby_sys;
+
+use lobby_res::*;
+use lobby_sys::*;
+
+pub const MIN_COOP_PLAYERS: usize = 2;
+pub const MAX_COOP_PLAYERS: usize = 4;
+pub const LOBBY_SLOT_COLOR: Color = Color::rgba(0.38, 0.0, 0.99, 0.9);
+pub const LOBBY_SLOT_COLOR_EMPTY: Color = Color::rgba(0.1, 0.1, 0.1, 0.8);
+
+pub struct LobbyPlugin;
+
+impl Plugin for LobbyPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Lobby>()
+            .add_systems(OnEnter(AppState::Lobby), spawn_lobby)
+            .add_systems(OnExit(AppState::Lobby), despawn_lobby)
+            .add_systems(
+                Update,
+                (join_lobby, update_lobby_slots)
+                    .chain()
+                    .run_if(in_state(AppState::Lobby)),
+            );
+    }
+}
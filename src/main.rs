@@ -1,36 +1,102 @@
-use std::time::Duration;
+use std::{path::Path, time::Duration};
 
 use bevy::window::WindowMode;
 use bevy::{asset::ChangeWatcher, prelude::*};
 
+mod actions;
 pub mod debug;
 mod game;
 pub mod game_over;
 pub mod gamepad;
+mod headless;
+mod leaderboard;
+mod lobby;
 mod main_menu;
+mod pause_menu;
+mod settings;
+mod storage;
 
+use actions::ActionsPlugin;
 use bevy_inspector_egui::quick::WorldInspectorPlugin;
 use debug::debug_res::*;
-use game::GamePlugin;
+use game::{
+    game_res::GameRng,
+    replay::replay_res::{Playback, Replay},
+    GamePlugin,
+};
 use game_over::GameOverPlugin;
 use gamepad::GamepadPlugin;
+use leaderboard::LeaderboardPlugin;
+use lobby::LobbyPlugin;
 use main_menu::MainMenuPlugin;
+use pause_menu::PauseMenuPlugin;
+use settings::{settings_res::Settings, SettingsPlugin};
 
 fn main() {
     // debug mode
     let args: Vec<String> = std::env::args().collect();
     let is_debug = args.iter().any(|arg| arg == "debug");
 
+    // simulate runs with a bot & print their metrics, `--headless --runs 20`
+    if args.iter().any(|arg| arg == "--headless") {
+        headless::run(&args);
+        return;
+    }
+
+    // fixed seed for reproducible runs, `--seed 1234`
+    let seed = args.iter().position(|arg| arg == "--seed").map(|idx| {
+        let seed = args
+            .get(idx + 1)
+            .ok_or("--seed needs a value".to_string())
+            .and_then(|seed| GameRng::parse_seed(seed));
+
+        seed.unwrap_or_else(|e| {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        })
+    });
+
+    // play a recorded run back, `--replay path/to/run.replay`
+    let playback = args
+        .iter()
+        .position(|arg| arg == "--replay")
+        .and_then(|idx| args.get(idx + 1))
+        .map(|path| match Replay::load(Path::new(path)) {
+            Ok(replay) => Playback::new(replay),
+            Err(e) => {
+                eprintln!("Could not load replay {}: {}", path, e);
+                std::process::exit(1);
+            }
+        });
+
+    let settings = Settings::load();
+
     let window_mode = if is_debug {
         WindowMode::Windowed
     } else {
-        WindowMode::BorderlessFullscreen
+        settings.window_mode.window_mode()
     };
 
-    App::new()
-        .insert_resource(EnableDebugMode::new(is_debug))
+    let primary_window = Window {
+        mode: window_mode,
+        resolution: settings.window_resolution(),
+        present_mode: settings.present_mode(),
+        ..default()
+    };
+
+    let mut app = App::new();
+
+    if let Some(playback) = playback {
+        app.insert_resource(playback);
+    }
+
+    app.insert_resource(EnableDebugMode::new(is_debug))
         .init_resource::<DebugProps>()
+        .insert_resource(settings)
+        .insert_resource(GameRng::new(seed))
         .add_state::<AppState>()
+        .add_state::<PauseState>()
+        .add_state::<SettingsState>()
         .add_plugins(
             DefaultPlugins
                 .set(AssetPlugin {
@@ -39,10 +105,7 @@ fn main() {
                     ..default()
                 })
                 .set(WindowPlugin {
-                    primary_window: Some(Window {
-                        mode: window_mode,
-                        ..default()
-                    }),
+                    primary_window: Some(primary_window),
                     ..default()
                 }),
         )
@@ -50,7 +113,17 @@ fn main() {
             WorldInspectorPlugin::new(),
             resource_equals(EnableDebugMode(true)),
         ))
-        .add_plugins((GamepadPlugin, GamePlugin, GameOverPlugin, MainMenuPlugin))
+        .add_plugins((
+            ActionsPlugin,
+            GamepadPlugin,
+            GamePlugin,
+            GameOverPlugin,
+            LeaderboardPlugin,
+            LobbyPlugin,
+            MainMenuPlugin,
+            PauseMenuPlugin,
+            SettingsPlugin,
+        ))
         .run();
 }
 
@@ -59,5 +132,28 @@ pub enum AppState {
     #[default]
     MainMenu,
     Game,
+    /// passed through on the way back into Game to start a fresh run
+    Restart,
     GameOver,
+    Leaderboard,
+    /// co-op lobby where players join with their own device
+    Lobby,
+}
+
+/// Only meaningful while in AppState::Game
+#[derive(States, Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
+pub enum PauseState {
+    #[default]
+    Running,
+    Paused,
+}
+
+/// Settings screen, shown on top of the main menu or the pause menu
+#[derive(States, Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
+pub enum SettingsState {
+    #[default]
+    Closed,
+    Open,
+    /// key & button rebinding page of the settings
+    Controls,
 }
@@ -8,3 +8,26 @@ pub struct MainMenu;
 
 #[derive(Component)]
 pub struct PlayBtn;
+
+#[derive(Component)]
+pub struct LeaderboardBtn;
+
+#[derive(Component)]
+pub struct SettingsBtn;
+
+#[derive(Component)]
+pub struct CoopBtn;
+
+#[derive(Component)]
+pub struct MapPrevBtn;
+
+#[derive(Component)]
+pub struct MapNextBtn;
+
+/// Preview image of the selected level
+#[derive(Component)]
+pub struct MapPreview;
+
+/// Name of the selected level
+#[derive(Component)]
+pub struct MapName;
@@ -0,0 +1,13 @@
+use bevy::prelude::*;
+
+use super::DEMO_IDLE_TIME;
+
+/// Time without any input on the main menu, the demo starts when it runs out
+#[derive(Resource)]
+pub struct MenuIdle(pub Timer);
+
+impl Default for MenuIdle {
+    fn default() -> Self {
+        Self(Timer::from_seconds(DEMO_IDLE_TIME, TimerMode::Once))
+    }
+}
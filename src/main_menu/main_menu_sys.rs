@@ -1,13 +1,63 @@
-use bevy::prelude::*;
+use bevy::{input::mouse::MouseMotion, prelude::*};
 
-use crate::{gamepad::gamepad_rcs::MyGamepad, AppState};
+use crate::{
+    actions::actions_res::{Action, InputActions},
+    game::{
+        bot::bot_res::{BotPlayers, Demo},
+        game_res::GameMode,
+        player::player_res::Party,
+        world::world_res::{Levels, SelectedLevel},
+    },
+    AppState, SettingsState,
+};
 
 use super::{
-    main_menu_cmps::{MainMenu, MainMenuCamera, PlayBtn},
+    main_menu_cmps::*, main_menu_res::MenuIdle, MAP_PREVIEW_HEIGHT, MAP_PREVIEW_WIDTH,
     PLAY_BTN_COLOR, PLAY_BTN_COLOR_HOVER,
 };
 
-pub fn spawn_main_menu(mut cmds: Commands, assets: Res<AssetServer>) {
+/// Play from the main menu is always solo, the co-op lobby sets up its own party
+pub fn reset_party(mut cmds: Commands, mut party: ResMut<Party>, mut mode: ResMut<GameMode>) {
+    *party = Party::default();
+    *mode = GameMode::Solo;
+    cmds.remove_resource::<Demo>();
+    cmds.remove_resource::<BotPlayers>();
+}
+
+pub fn reset_menu_idle(mut idle: ResMut<MenuIdle>) {
+    idle.0.reset();
+}
+
+/// Start a demo run played by a bot once the menu has been left alone for a while
+pub fn start_demo(
+    mut cmds: Commands,
+    time: Res<Time>,
+    actions: Res<InputActions>,
+    mut motion_evr: EventReader<MouseMotion>,
+    mouse: Res<Input<MouseButton>>,
+    mut idle: ResMut<MenuIdle>,
+    mut next_app_state: ResMut<NextState<AppState>>,
+) {
+    // pointing at or clicking through the menu counts as using it too
+    let mouse_used = motion_evr.iter().count() > 0 || mouse.get_pressed().next().is_some();
+
+    if actions.any_just_pressed() || actions.movement() != Vec2::ZERO || mouse_used {
+        idle.0.reset();
+    }
+
+    if idle.0.tick(time.delta()).just_finished() {
+        cmds.insert_resource(Demo);
+        cmds.insert_resource(BotPlayers);
+        next_app_state.set(AppState::Game);
+    }
+}
+
+pub fn spawn_main_menu(
+    mut cmds: Commands,
+    assets: Res<AssetServer>,
+    levels: Res<Levels>,
+    selected: Res<SelectedLevel>,
+) {
     let img_container = (
         ImageBundle {
             image: assets.load("imgs/main_menu_background.png").into(),
@@ -67,6 +117,109 @@ pub fn spawn_main_menu(mut cmds: Commands, assets: Res<AssetServer>) {
         Name::new("Play Button Image"),
     );
 
+    let leaderboard_btn = (
+        ButtonBundle {
+            background_color: PLAY_BTN_COLOR.into(),
+            style: Style {
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                top: Val::Percent(30.0),
+                margin: UiRect::left(Val::Px(25.0)),
+                width: Val::Px(300.0),
+                height: Val::Px(75.0),
+                ..default()
+            },
+            ..default()
+        },
+        LeaderboardBtn,
+        Name::new("Leaderboard Button"),
+    );
+
+    let leaderboard_txt = (
+        TextBundle::from_section(
+            "Leaderboard - ",
+            TextStyle {
+                color: Color::WHITE,
+                font: assets.load("fonts/PermanentMarker-Regular.ttf"),
+                font_size: 40.0,
+            },
+        ),
+        Name::new("Leaderboard Text"),
+    );
+
+    let leaderboard_btn_img = (
+        ImageBundle {
+            image: assets.load("imgs/y_button.png").into(),
+            style: Style {
+                width: Val::Px(35.0),
+                height: Val::Px(35.0),
+                ..default()
+            },
+            ..default()
+        },
+        Name::new("Leaderboard Button Image"),
+    );
+
+    let coop_btn = (
+        ButtonBundle {
+            background_color: PLAY_BTN_COLOR.into(),
+            style: Style {
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                top: Val::Percent(30.0),
+                margin: UiRect::left(Val::Px(25.0)),
+                width: Val::Px(150.0),
+                height: Val::Px(75.0),
+                ..default()
+            },
+            ..default()
+        },
+        CoopBtn,
+        Name::new("Co-op Button"),
+    );
+
+    let coop_txt = (
+        TextBundle::from_section(
+            "Co-op",
+            TextStyle {
+                color: Color::WHITE,
+                font: assets.load("fonts/PermanentMarker-Regular.ttf"),
+                font_size: 40.0,
+            },
+        ),
+        Name::new("Co-op Text"),
+    );
+
+    let settings_btn = (
+        ButtonBundle {
+            background_color: PLAY_BTN_COLOR.into(),
+            style: Style {
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                top: Val::Percent(30.0),
+                margin: UiRect::left(Val::Px(25.0)),
+                width: Val::Px(150.0),
+                height: Val::Px(75.0),
+                ..default()
+            },
+            ..default()
+        },
+        SettingsBtn,
+        Name::new("Settings Button"),
+    );
+
+    let settings_txt = (
+        TextBundle::from_section(
+            "Settings",
+            TextStyle {
+                color: Color::WHITE,
+                font: assets.load("fonts/PermanentMarker-Regular.ttf"),
+                font_size: 40.0,
+            },
+        ),
+        Name::new("Settings Text"),
+    );
+
     let title_txt = (
         TextBundle {
             text: Text::from_section(
@@ -89,13 +242,129 @@ pub fn spawn_main_menu(mut cmds: Commands, assets: Res<AssetServer>) {
         Name::new("Zombeats Text"),
     );
 
+    let level = levels.find_or_first(&selected.0);
+
+    let map_picker = (
+        NodeBundle {
+            style: Style {
+                align_items: AlignItems::Center,
+                flex_direction: FlexDirection::Column,
+                position_type: PositionType::Absolute,
+                bottom: Val::Percent(8.0),
+                ..default()
+            },
+            ..default()
+        },
+        Name::new("Map Picker"),
+    );
+
+    let map_preview = (
+        ImageBundle {
+            image: level
+                .map(|level| assets.load(&level.preview))
+                .unwrap_or_default()
+                .into(),
+            style: Style {
+                width: Val::Px(MAP_PREVIEW_WIDTH),
+                height: Val::Px(MAP_PREVIEW_HEIGHT),
+                margin: UiRect::bottom(Val::Px(10.0)),
+                ..default()
+            },
+            ..default()
+        },
+        MapPreview,
+        Name::new("Map Preview Image"),
+    );
+
+    let map_row = (
+        NodeBundle {
+            style: Style {
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            ..default()
+        },
+        Name::new("Map Row"),
+    );
+
+    let map_btn = |label: &str| {
+        (
+            ButtonBundle {
+                background_color: PLAY_BTN_COLOR.into(),
+                style: Style {
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    width: Val::Px(50.0),
+                    height: Val::Px(50.0),
+                    ..default()
+                },
+                ..default()
+            },
+            Name::new(format!("Map {} Button", label)),
+        )
+    };
+
+    let map_btn_txt = |label: &str| {
+        TextBundle::from_section(
+            label,
+            TextStyle {
+                color: Color::WHITE,
+                font: assets.load("fonts/PermanentMarker-Regular.ttf"),
+                font_size: 40.0,
+            },
+        )
+    };
+
+    let map_name_txt = (
+        TextBundle::from_section(
+            level.map(|level| level.name.clone()).unwrap_or_default(),
+            TextStyle {
+                color: Color::WHITE,
+                font: assets.load("fonts/PermanentMarker-Regular.ttf"),
+                font_size: 40.0,
+            },
+        )
+        .with_style(Style {
+            margin: UiRect::horizontal(Val::Px(25.0)),
+            ..default()
+        }),
+        MapName,
+        Name::new("Map Name Text"),
+    );
+
     cmds.spawn((Camera3dBundle::default(), MainMenuCamera));
     cmds.spawn(img_container).with_children(|parent| {
         parent.spawn(title_txt);
+        parent.spawn(map_picker).with_children(|parent| {
+            parent.spawn(map_preview);
+            parent.spawn(map_row).with_children(|parent| {
+                parent
+                    .spawn((map_btn("Previous"), MapPrevBtn))
+                    .with_children(|parent| {
+                        parent.spawn(map_btn_txt("<"));
+                    });
+                parent.spawn(map_name_txt);
+                parent
+                    .spawn((map_btn("Next"), MapNextBtn))
+                    .with_children(|parent| {
+                        parent.spawn(map_btn_txt(">"));
+                    });
+            });
+        });
         parent.spawn(play_btn).with_children(|parent| {
             parent.spawn(play_txt);
             parent.spawn(play_btn_img);
         });
+        parent.spawn(coop_btn).with_children(|parent| {
+            parent.spawn(coop_txt);
+        });
+        parent.spawn(leaderboard_btn).with_children(|parent| {
+            parent.spawn(leaderboard_txt);
+            parent.spawn(leaderboard_btn_img);
+        });
+        parent.spawn(settings_btn).with_children(|parent| {
+            parent.spawn(settings_txt);
+        });
     });
 }
 
@@ -113,22 +382,12 @@ pub fn despawn_menu(
     }
 }
 
-pub fn select_play_gamepad(
-    btns: Res<Input<GamepadButton>>,
-    cur_app_state_res: Res<State<AppState>>,
+pub fn select_play_action(
+    actions: Res<InputActions>,
     mut next_app_state: ResMut<NextState<AppState>>,
-    my_gamepad: Option<Res<MyGamepad>>,
 ) {
-    let gamepad_input = my_gamepad
-        .map(|gp| btns.just_pressed(GamepadButton::new(gp.gamepad, GamepadButtonType::South)))
-        .unwrap_or(false);
-
-    let current_state = cur_app_state_res.get();
-
-    if gamepad_input {
-        if current_state != &AppState::Game {
-            next_app_state.set(AppState::Game);
-        }
+    if actions.just_pressed(Action::Confirm) {
+        next_app_state.set(AppState::Game);
     }
 }
 
@@ -147,3 +406,144 @@ pub fn select_play_mouse(
         }
     }
 }
+
+/// The co-op action opens the lobby, there is no button image for it
+pub fn select_coop_action(
+    actions: Res<InputActions>,
+    mut next_app_state: ResMut<NextState<AppState>>,
+) {
+    if actions.just_pressed(Action::Coop) {
+        next_app_state.set(AppState::Lobby);
+    }
+}
+
+type CoopBtnInteraction = (Changed<Interaction>, With<CoopBtn>);
+
+pub fn select_coop_mouse(
+    mut interact_q: Query<(&Interaction, &mut BackgroundColor), CoopBtnInteraction>,
+    mut next_app_state: ResMut<NextState<AppState>>,
+) {
+    for (interaction, mut background_clr) in &mut interact_q {
+        match *interaction {
+            Interaction::Pressed => next_app_state.set(AppState::Lobby),
+            Interaction::Hovered => *background_clr = PLAY_BTN_COLOR_HOVER.into(),
+            Interaction::None => *background_clr = PLAY_BTN_COLOR.into(),
+        }
+    }
+}
+
+pub fn select_leaderboard_action(
+    actions: Res<InputActions>,
+    mut next_app_state: ResMut<NextState<AppState>>,
+) {
+    if actions.just_pressed(Action::Alternate) {
+        next_app_state.set(AppState::Leaderboard);
+    }
+}
+
+type LeaderboardBtnInteraction = (Changed<Interaction>, With<LeaderboardBtn>);
+
+pub fn select_leaderboard_mouse(
+    mut interact_q: Query<(&Interaction, &mut BackgroundColor), LeaderboardBtnInteraction>,
+    mut next_app_state: ResMut<NextState<AppState>>,
+) {
+    for (interaction, mut background_clr) in &mut interact_q {
+        match *interaction {
+            Interaction::Pressed => next_app_state.set(AppState::Leaderboard),
+            Interaction::Hovered => *background_clr = PLAY_BTN_COLOR_HOVER.into(),
+            Interaction::None => *background_clr = PLAY_BTN_COLOR.into(),
+        }
+    }
+}
+
+/// The pause action opens the settings, there is no button image for it
+pub fn select_settings_action(
+    actions: Res<InputActions>,
+    mut next_settings_state: ResMut<NextState<SettingsState>>,
+) {
+    if actions.just_pressed(Action::Pause) {
+        next_settings_state.set(SettingsState::Open);
+    }
+}
+
+type SettingsBtnInteraction = (Changed<Interaction>, With<SettingsBtn>);
+
+pub fn select_settings_mouse(
+    mut interact_q: Query<(&Interaction, &mut BackgroundColor), SettingsBtnInteraction>,
+    mut next_settings_state: ResMut<NextState<SettingsState>>,
+) {
+    for (interaction, mut background_clr) in &mut interact_q {
+        match *interaction {
+            Interaction::Pressed => next_settings_state.set(SettingsState::Open),
+            Interaction::Hovered => *background_clr = PLAY_BTN_COLOR_HOVER.into(),
+            Interaction::None => *background_clr = PLAY_BTN_COLOR.into(),
+        }
+    }
+}
+
+/// Cycle through the levels by `step`, wrapping around at both ends
+fn cycle_level(selected: &mut SelectedLevel, levels: &Levels, step: isize) {
+    let count = levels.0.len() as isize;
+    if count > 0 {
+        let current = levels.0.iter().position(|level| level.name == selected.0);
+        let idx = (current.unwrap_or(0) as isize + step).rem_euclid(count) as usize;
+        selected.0 = levels.0[idx].name.clone();
+    }
+}
+
+pub fn select_map_action(
+    actions: Res<InputActions>,
+    levels: Res<Levels>,
+    mut selected: ResMut<SelectedLevel>,
+) {
+    if actions.just_pressed(Action::MenuLeft) {
+        cycle_level(&mut selected, &levels, -1);
+    }
+
+    if actions.just_pressed(Action::MenuRight) {
+        cycle_level(&mut selected, &levels, 1);
+    }
+}
+
+type MapBtnInteraction = (
+    Changed<Interaction>,
+    Or<(With<MapPrevBtn>, With<MapNextBtn>)>,
+);
+
+pub fn select_map_mouse(
+    mut interact_q: Query<
+        (&Interaction, &mut BackgroundColor, Option<&MapPrevBtn>),
+        MapBtnInteraction,
+    >,
+    levels: Res<Levels>,
+    mut selected: ResMut<SelectedLevel>,
+) {
+    for (interaction, mut background_clr, prev) in &mut interact_q {
+        match *interaction {
+            Interaction::Pressed => {
+                cycle_level(&mut selected, &levels, if prev.is_some() { -1 } else { 1 })
+            }
+            Interaction::Hovered => *background_clr = PLAY_BTN_COLOR_HOVER.into(),
+            Interaction::None => *background_clr = PLAY_BTN_COLOR.into(),
+        }
+    }
+}
+
+/// Show the preview & name of the selected level
+pub fn update_map_picker(
+    assets: Res<AssetServer>,
+    levels: Res<Levels>,
+    selected: Res<SelectedLevel>,
+    mut preview_q: Query<&mut UiImage, With<MapPreview>>,
+    mut name_q: Query<&mut Text, With<MapName>>,
+) {
+    let Some(level) = levels.find_or_first(&selected.0) else { return };
+
+    for mut preview in preview_q.iter_mut() {
+        preview.texture = assets.load(&level.preview);
+    }
+
+    for mut name in name_q.iter_mut() {
+        name.sections[0].value = level.name.clone();
+    }
+}
@@ -1,24 +1,73 @@
 use bevy::prelude::*;
 
 pub mod main_menu_cmps;
+mod main_menu_res;
 mod main_menu_sys;
 
+use main_menu_res::*;
 use main_menu_sys::*;
 
-use crate::AppState;
+use crate::{
+    game::{
+        config::config_res::GameConfig,
+        replay::replay_res::Playback,
+        world::world_res::{Level, Levels, SelectedLevel},
+    },
+    AppState, SettingsState,
+};
 
 pub const PLAY_BTN_COLOR: Color = Color::rgba(0.38, 0.0, 0.99, 0.9);
 pub const PLAY_BTN_COLOR_HOVER: Color = Color::rgb(0.5, 0.0, 1.0);
+pub const MAP_PREVIEW_WIDTH: f32 = 240.0;
+pub const MAP_PREVIEW_HEIGHT: f32 = 135.0;
+/// seconds on the main menu without input before the demo starts
+pub const DEMO_IDLE_TIME: f32 = 30.0;
 
 pub struct MainMenuPlugin;
 
 impl Plugin for MainMenuPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(AppState::MainMenu), spawn_main_menu)
+        app.init_resource::<MenuIdle>()
+            .add_systems(
+                OnEnter(AppState::MainMenu),
+                (spawn_main_menu, reset_party, reset_menu_idle),
+            )
             .add_systems(OnExit(AppState::MainMenu), despawn_menu)
             .add_systems(
                 Update,
-                (select_play_gamepad, select_play_mouse).run_if(in_state(AppState::MainMenu)),
+                (
+                    select_play_action,
+                    select_play_mouse,
+                    select_coop_action,
+                    select_coop_mouse,
+                    select_map_action,
+                    select_map_mouse,
+                    start_demo.run_if(not(resource_exists::<Playback>())),
+                )
+                    .run_if(in_state(AppState::MainMenu))
+                    .run_if(in_state(SettingsState::Closed))
+                    // the game can't start until the gameplay config & level are loaded
+                    .run_if(resource_exists::<GameConfig>())
+                    .run_if(resource_exists::<Level>()),
+            )
+            .add_systems(
+                Update,
+                (
+                    select_leaderboard_action,
+                    select_leaderboard_mouse,
+                    select_settings_action,
+                    select_settings_mouse,
+                )
+                    .run_if(in_state(AppState::MainMenu))
+                    .run_if(in_state(SettingsState::Closed)),
+            )
+            .add_systems(
+                Update,
+                update_map_picker
+                    .run_if(in_state(AppState::MainMenu))
+                    .run_if(
+                        resource_changed::<Levels>().or_else(resource_changed::<SelectedLevel>()),
+                    ),
             );
     }
 }
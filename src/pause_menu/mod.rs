@@ -0,0 +1,44 @@
+use bevy::prelude::*;
+
+use crate::{AppState, PauseState, SettingsState};
+
+pub mod pause_menu_cmps;
+pub mod pause_menu_evs;
+pub mod pause_menu_res;
+mod pause_menu_sys;
+
+use pause_menu_evs::*;
+use pause_menu_res::*;
+use pause_menu_sys::*;
+
+pub const PAUSE_BTN_COLOR: Color = Color::rgba(0.38, 0.0, 0.99, 0.9);
+pub const PAUSE_BTN_COLOR_SELECTED: Color = Color::rgb(0.5, 0.0, 1.0);
+pub const PAUSE_BACKGROUND_COLOR: Color = Color::rgba(0.0, 0.0, 0.0, 0.6);
+
+pub struct PauseMenuPlugin;
+
+impl Plugin for PauseMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PauseSelection>()
+            .add_event::<PauseBtnPressed>()
+            .add_systems(OnEnter(PauseState::Paused), (spawn_pause_menu, freeze_game))
+            .add_systems(
+                OnExit(PauseState::Paused),
+                (despawn_pause_menu, unfreeze_game),
+            )
+            .add_systems(
+                Update,
+                (
+                    navigate_pause_menu_actions,
+                    navigate_pause_menu_mouse,
+                    highlight_pause_btns,
+                    press_pause_btn,
+                )
+                    .chain()
+                    .run_if(in_state(AppState::Game))
+                    .run_if(in_state(PauseState::Paused))
+                    // the settings screen takes over input while it is open
+                    .run_if(in_state(SettingsState::Closed)),
+            );
+    }
+}
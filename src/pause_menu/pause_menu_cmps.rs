@@ -0,0 +1,31 @@
+use bevy::prelude::*;
+
+#[derive(Component)]
+pub struct PauseMenu;
+
+#[derive(Component, Clone, Copy, PartialEq)]
+pub enum PauseBtn {
+    Resume,
+    Restart,
+    Settings,
+    Quit,
+}
+
+impl PauseBtn {
+    /// buttons in the order they are listed in the menu
+    pub const ALL: [PauseBtn; 4] = [
+        PauseBtn::Resume,
+        PauseBtn::Restart,
+        PauseBtn::Settings,
+        PauseBtn::Quit,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PauseBtn::Resume => "Resume",
+            PauseBtn::Restart => "Restart",
+            PauseBtn::Settings => "Settings",
+            PauseBtn::Quit => "Quit to Menu",
+        }
+    }
+}
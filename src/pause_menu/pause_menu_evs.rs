@@ -0,0 +1,7 @@
+use bevy::prelude::Event;
+
+use super::pause_menu_cmps::PauseBtn;
+
+/// A pause menu button was confirmed with any input device
+#[derive(Event)]
+pub struct PauseBtnPressed(pub PauseBtn);
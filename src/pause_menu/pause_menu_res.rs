@@ -0,0 +1,19 @@
+use bevy::prelude::*;
+
+use super::pause_menu_cmps::PauseBtn;
+
+/// Button highlighted in the pause menu, confirmed with Enter or the south face button
+#[derive(Resource, Default)]
+pub struct PauseSelection(pub usize);
+
+impl PauseSelection {
+    pub fn btn(&self) -> PauseBtn {
+        PauseBtn::ALL[self.0]
+    }
+
+    /// move the selection up or down, wrapping around at either end
+    pub fn step(&mut self, step: i32) {
+        let len = PauseBtn::ALL.len() as i32;
+        self.0 = (self.0 as i32 + step).rem_euclid(len) as usize;
+    }
+}
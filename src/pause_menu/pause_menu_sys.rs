@@ -0,0 +1,218 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::RapierConfiguration;
+use bevy_third_person_camera::ThirdPersonCamera;
+
+use crate::{
+    actions::actions_res::{Action, InputActions},
+    game::game_res::GameTime,
+    AppState, PauseState, SettingsState,
+};
+
+use super::{
+    pause_menu_cmps::*, pause_menu_evs::PauseBtnPressed, pause_menu_res::PauseSelection,
+    PAUSE_BACKGROUND_COLOR, PAUSE_BTN_COLOR, PAUSE_BTN_COLOR_SELECTED,
+};
+
+pub fn spawn_pause_menu(
+    mut cmds: Commands,
+    assets: Res<AssetServer>,
+    mut selection: ResMut<PauseSelection>,
+) {
+    *selection = PauseSelection::default();
+
+    let container = (
+        NodeBundle {
+            background_color: PAUSE_BACKGROUND_COLOR.into(),
+            style: Style {
+                align_items: AlignItems::Center,
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                ..default()
+            },
+            ..default()
+        },
+        PauseMenu,
+        Name::new("Pause Menu"),
+    );
+
+    let title_txt = (
+        TextBundle::from_section(
+            "Paused",
+            TextStyle {
+                color: Color::WHITE,
+                font: assets.load("fonts/PermanentMarker-Regular.ttf"),
+                font_size: 60.0,
+            },
+        ),
+        Name::new("Paused Text"),
+    );
+
+    cmds.spawn(container).with_children(|parent| {
+        parent.spawn(title_txt);
+
+        for (idx, btn) in PauseBtn::ALL.into_iter().enumerate() {
+            let color = if idx == selection.0 {
+                PAUSE_BTN_COLOR_SELECTED
+            } else {
+                PAUSE_BTN_COLOR
+            };
+
+            let btn_bundle = (
+                ButtonBundle {
+                    background_color: color.into(),
+                    style: Style {
+                        align_items: AlignItems::Center,
+                        justify_content: JustifyContent::Center,
+                        width: Val::Px(300.0),
+                        height: Val::Px(75.0),
+                        margin: UiRect::top(Val::Px(20.0)),
+                        ..default()
+                    },
+                    ..default()
+                },
+                btn,
+                Name::new(format!("{} Button", btn.label())),
+            );
+
+            let btn_txt = TextBundle::from_section(
+                btn.label(),
+                TextStyle {
+                    color: Color::WHITE,
+                    font: assets.load("fonts/PermanentMarker-Regular.ttf"),
+                    font_size: 40.0,
+                },
+            );
+
+            parent.spawn(btn_bundle).with_children(|parent| {
+                parent.spawn(btn_txt);
+            });
+        }
+    });
+}
+
+pub fn despawn_pause_menu(mut cmds: Commands, menu_q: Query<Entity, With<PauseMenu>>) {
+    for ent in menu_q.iter() {
+        cmds.entity(ent).despawn_recursive();
+    }
+}
+
+/// Stop game time, physics and audio, and free the cursor for the menu
+pub fn freeze_game(
+    mut time: ResMut<Time>,
+    mut rapier_config: ResMut<RapierConfiguration>,
+    sink_q: Query<&AudioSink>,
+    mut cam_q: Query<&mut ThirdPersonCamera>,
+) {
+    time.pause();
+    rapier_config.physics_pipeline_active = false;
+
+    for sink in sink_q.iter() {
+        sink.pause();
+    }
+
+    for mut cam in cam_q.iter_mut() {
+        cam.cursor_lock_active = false;
+        cam.cursor_lock_toggle_enabled = false;
+    }
+}
+
+pub fn unfreeze_game(
+    mut time: ResMut<Time>,
+    mut rapier_config: ResMut<RapierConfiguration>,
+    sink_q: Query<&AudioSink>,
+    mut cam_q: Query<&mut ThirdPersonCamera>,
+) {
+    time.unpause();
+    rapier_config.physics_pipeline_active = true;
+
+    for sink in sink_q.iter() {
+        sink.play();
+    }
+
+    for mut cam in cam_q.iter_mut() {
+        cam.cursor_lock_active = true;
+        cam.cursor_lock_toggle_enabled = true;
+    }
+}
+
+/// Menu up & down move the selection, Confirm presses it and Back resumes
+pub fn navigate_pause_menu_actions(
+    actions: Res<InputActions>,
+    mut selection: ResMut<PauseSelection>,
+    mut pressed_evw: EventWriter<PauseBtnPressed>,
+) {
+    if actions.just_pressed(Action::MenuUp) {
+        selection.step(-1);
+    }
+
+    if actions.just_pressed(Action::MenuDown) {
+        selection.step(1);
+    }
+
+    if actions.just_pressed(Action::Confirm) {
+        pressed_evw.send(PauseBtnPressed(selection.btn()));
+    }
+
+    if actions.just_pressed(Action::Back) {
+        pressed_evw.send(PauseBtnPressed(PauseBtn::Resume));
+    }
+}
+
+/// Hovering a button selects it, clicking confirms it
+pub fn navigate_pause_menu_mouse(
+    interact_q: Query<(&Interaction, &PauseBtn), Changed<Interaction>>,
+    mut selection: ResMut<PauseSelection>,
+    mut pressed_evw: EventWriter<PauseBtnPressed>,
+) {
+    for (interaction, btn) in interact_q.iter() {
+        let Some(idx) = PauseBtn::ALL.iter().position(|b| b == btn) else {
+            continue;
+        };
+
+        match *interaction {
+            Interaction::Pressed => pressed_evw.send(PauseBtnPressed(*btn)),
+            Interaction::Hovered => selection.0 = idx,
+            Interaction::None => (),
+        }
+    }
+}
+
+pub fn highlight_pause_btns(
+    selection: Res<PauseSelection>,
+    mut btn_q: Query<(&PauseBtn, &mut BackgroundColor)>,
+) {
+    if !selection.is_changed() {
+        return;
+    }
+
+    for (btn, mut background_clr) in btn_q.iter_mut() {
+        *background_clr = if *btn == selection.btn() {
+            PAUSE_BTN_COLOR_SELECTED.into()
+        } else {
+            PAUSE_BTN_COLOR.into()
+        };
+    }
+}
+
+pub fn press_pause_btn(
+    mut pressed_evr: EventReader<PauseBtnPressed>,
+    mut next_app_state: ResMut<NextState<AppState>>,
+    mut next_pause_state: ResMut<NextState<PauseState>>,
+    mut next_settings_state: ResMut<NextState<SettingsState>>,
+    mut game_time: ResMut<GameTime>,
+) {
+    for ev in pressed_evr.iter() {
+        match ev.0 {
+            PauseBtn::Resume => next_pause_state.set(PauseState::Running),
+            PauseBtn::Restart => next_app_state.set(AppState::Restart),
+            PauseBtn::Settings => next_settings_state.set(SettingsState::Open),
+            PauseBtn::Quit => {
+                next_app_state.set(AppState::MainMenu);
+                game_time.0.reset(); // reset stopwatch
+            }
+        }
+    }
+}
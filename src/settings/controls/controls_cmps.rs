@@ -0,0 +1,47 @@
+use bevy::prelude::*;
+
+use crate::actions::actions_res::Action;
+
+#[derive(Component)]
+pub struct ControlsMenu;
+
+#[derive(Component, Clone, Copy, PartialEq)]
+pub enum ControlsItem {
+    Rebind(Action),
+    Reset,
+    Back,
+}
+
+impl ControlsItem {
+    /// rows in the order they are listed in the menu, menu navigation can't be rebound
+    pub const ALL: [ControlsItem; 16] = [
+        ControlsItem::Rebind(Action::MoveForward),
+        ControlsItem::Rebind(Action::MoveBack),
+        ControlsItem::Rebind(Action::MoveLeft),
+        ControlsItem::Rebind(Action::MoveRight),
+        ControlsItem::Rebind(Action::Sprint),
+        ControlsItem::Rebind(Action::Fire),
+        ControlsItem::Rebind(Action::Reload),
+        ControlsItem::Rebind(Action::NextWeapon),
+        ControlsItem::Rebind(Action::PrevWeapon),
+        ControlsItem::Rebind(Action::Weapon1),
+        ControlsItem::Rebind(Action::Weapon2),
+        ControlsItem::Rebind(Action::Weapon3),
+        ControlsItem::Rebind(Action::Weapon4),
+        ControlsItem::Rebind(Action::Pause),
+        ControlsItem::Reset,
+        ControlsItem::Back,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ControlsItem::Rebind(action) => action.label(),
+            ControlsItem::Reset => "Reset to Defaults",
+            ControlsItem::Back => "Back",
+        }
+    }
+}
+
+/// Text listing the bindings of an action
+#[derive(Component)]
+pub struct BindingTxt(pub Action);
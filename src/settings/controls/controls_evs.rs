@@ -0,0 +1,7 @@
+use bevy::prelude::Event;
+
+use super::controls_cmps::ControlsItem;
+
+/// A controls menu row was confirmed with any input device
+#[derive(Event)]
+pub struct ControlsItemPressed(pub ControlsItem);
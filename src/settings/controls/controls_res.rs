@@ -0,0 +1,25 @@
+use bevy::prelude::*;
+
+use crate::actions::actions_res::Action;
+
+use super::controls_cmps::ControlsItem;
+
+/// Row highlighted in the controls menu
+#[derive(Resource, Default)]
+pub struct ControlsSelection(pub usize);
+
+impl ControlsSelection {
+    pub fn item(&self) -> ControlsItem {
+        ControlsItem::ALL[self.0]
+    }
+
+    /// move the selection up or down, wrapping around at either end
+    pub fn step(&mut self, step: i32) {
+        let len = ControlsItem::ALL.len() as i32;
+        self.0 = (self.0 as i32 + step).rem_euclid(len) as usize;
+    }
+}
+
+/// Action waiting for the next key or button press to bind it
+#[derive(Resource, Default)]
+pub struct Rebinding(pub Option<Action>);
@@ -0,0 +1,263 @@
+use bevy::{input::mouse::MouseWheel, prelude::*};
+
+use crate::{
+    actions::actions_res::{Action, Binding, Bindings, InputActions},
+    gamepad::gamepad_rcs::MyGamepad,
+    settings::{SETTINGS_BACKGROUND_COLOR, SETTINGS_BTN_COLOR, SETTINGS_BTN_COLOR_SELECTED},
+    SettingsState,
+};
+
+use super::{controls_cmps::*, controls_evs::ControlsItemPressed, controls_res::*, REBIND_PROMPT};
+
+pub fn spawn_controls_menu(
+    mut cmds: Commands,
+    assets: Res<AssetServer>,
+    bindings: Res<Bindings>,
+    mut selection: ResMut<ControlsSelection>,
+    mut rebinding: ResMut<Rebinding>,
+) {
+    *selection = ControlsSelection::default();
+    *rebinding = Rebinding::default();
+    let font = assets.load("fonts/PermanentMarker-Regular.ttf");
+
+    let container = (
+        NodeBundle {
+            background_color: SETTINGS_BACKGROUND_COLOR.into(),
+            style: Style {
+                align_items: AlignItems::Center,
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                ..default()
+            },
+            z_index: ZIndex::Global(10),
+            ..default()
+        },
+        ControlsMenu,
+        Name::new("Controls Menu"),
+    );
+
+    let title_txt = (
+        TextBundle::from_section(
+            "Controls",
+            TextStyle {
+                color: Color::WHITE,
+                font: font.clone(),
+                font_size: 60.0,
+            },
+        ),
+        Name::new("Controls Text"),
+    );
+
+    let txt_style = TextStyle {
+        color: Color::WHITE,
+        font,
+        font_size: 26.0,
+    };
+
+    cmds.spawn(container).with_children(|parent| {
+        parent.spawn(title_txt);
+
+        for (idx, item) in ControlsItem::ALL.into_iter().enumerate() {
+            let color = if idx == selection.0 {
+                SETTINGS_BTN_COLOR_SELECTED
+            } else {
+                SETTINGS_BTN_COLOR
+            };
+
+            let row = (
+                ButtonBundle {
+                    background_color: color.into(),
+                    style: Style {
+                        align_items: AlignItems::Center,
+                        justify_content: JustifyContent::SpaceBetween,
+                        width: Val::Px(600.0),
+                        height: Val::Px(36.0),
+                        margin: UiRect::top(Val::Px(6.0)),
+                        padding: UiRect::horizontal(Val::Px(15.0)),
+                        ..default()
+                    },
+                    ..default()
+                },
+                item,
+                Name::new(format!("{} Row", item.label())),
+            );
+
+            parent.spawn(row).with_children(|parent| {
+                parent.spawn(TextBundle::from_section(item.label(), txt_style.clone()));
+
+                if let ControlsItem::Rebind(action) = item {
+                    parent.spawn((
+                        TextBundle::from_section(bindings.describe(action), txt_style.clone()),
+                        BindingTxt(action),
+                    ));
+                }
+            });
+        }
+    });
+}
+
+pub fn despawn_controls_menu(mut cmds: Commands, menu_q: Query<Entity, With<ControlsMenu>>) {
+    for ent in menu_q.iter() {
+        cmds.entity(ent).despawn_recursive();
+    }
+}
+
+pub fn save_bindings(bindings: Res<Bindings>) {
+    bindings.save();
+}
+
+pub fn rebinding_active(rebinding: Res<Rebinding>) -> bool {
+    rebinding.0.is_some()
+}
+
+pub fn rebinding_inactive(rebinding: Res<Rebinding>) -> bool {
+    rebinding.0.is_none()
+}
+
+/// Menu up & down move the selection, Confirm presses it and Back returns to the settings
+pub fn navigate_controls_actions(
+    actions: Res<InputActions>,
+    mut selection: ResMut<ControlsSelection>,
+    mut pressed_evw: EventWriter<ControlsItemPressed>,
+) {
+    if actions.just_pressed(Action::MenuUp) {
+        selection.step(-1);
+    }
+
+    if actions.just_pressed(Action::MenuDown) {
+        selection.step(1);
+    }
+
+    if actions.just_pressed(Action::Confirm) {
+        pressed_evw.send(ControlsItemPressed(selection.item()));
+    }
+
+    if actions.just_pressed(Action::Back) {
+        pressed_evw.send(ControlsItemPressed(ControlsItem::Back));
+    }
+}
+
+/// Hovering a row selects it, clicking confirms it
+pub fn navigate_controls_mouse(
+    interact_q: Query<(&Interaction, &ControlsItem), Changed<Interaction>>,
+    mut selection: ResMut<ControlsSelection>,
+    mut pressed_evw: EventWriter<ControlsItemPressed>,
+) {
+    for (interaction, item) in interact_q.iter() {
+        let Some(idx) = ControlsItem::ALL.iter().position(|i| i == item) else {
+            continue;
+        };
+
+        match *interaction {
+            Interaction::Pressed => pressed_evw.send(ControlsItemPressed(*item)),
+            Interaction::Hovered => selection.0 = idx,
+            Interaction::None => (),
+        }
+    }
+}
+
+pub fn highlight_controls_rows(
+    selection: Res<ControlsSelection>,
+    mut row_q: Query<(&ControlsItem, &mut BackgroundColor)>,
+) {
+    if !selection.is_changed() {
+        return;
+    }
+
+    for (item, mut background_clr) in row_q.iter_mut() {
+        *background_clr = if *item == selection.item() {
+            SETTINGS_BTN_COLOR_SELECTED.into()
+        } else {
+            SETTINGS_BTN_COLOR.into()
+        };
+    }
+}
+
+pub fn press_controls_item(
+    mut pressed_evr: EventReader<ControlsItemPressed>,
+    mut bindings: ResMut<Bindings>,
+    mut rebinding: ResMut<Rebinding>,
+    mut next_settings_state: ResMut<NextState<SettingsState>>,
+) {
+    for ev in pressed_evr.iter() {
+        match ev.0 {
+            ControlsItem::Rebind(action) => rebinding.0 = Some(action),
+            ControlsItem::Reset => *bindings = Bindings::default(),
+            ControlsItem::Back => next_settings_state.set(SettingsState::Open),
+        }
+    }
+}
+
+/// Bind the next key, mouse or gamepad button to the waiting action, Escape cancels
+pub fn capture_binding(
+    keys: Res<Input<KeyCode>>,
+    mouse: Res<Input<MouseButton>>,
+    btns: Res<Input<GamepadButton>>,
+    mut wheel_evr: EventReader<MouseWheel>,
+    my_gamepad: Option<Res<MyGamepad>>,
+    mut bindings: ResMut<Bindings>,
+    mut rebinding: ResMut<Rebinding>,
+) {
+    let scroll: f32 = wheel_evr.iter().map(|ev| ev.y).sum();
+
+    // ignore the press that started the rebind
+    if rebinding.is_changed() {
+        return;
+    }
+    let Some(action) = rebinding.0 else { return };
+
+    if keys.just_pressed(KeyCode::Escape) {
+        rebinding.0 = None;
+        return;
+    }
+
+    let gamepad = my_gamepad.map(|gp| gp.gamepad);
+    let binding = keys
+        .get_just_pressed()
+        .next()
+        .map(|key| Binding::Key(*key))
+        .or_else(|| {
+            mouse
+                .get_just_pressed()
+                .next()
+                .map(|btn| Binding::Mouse(*btn))
+        })
+        .or_else(|| {
+            btns.get_just_pressed()
+                .find(|btn| Some(btn.gamepad) == gamepad)
+                .map(|btn| Binding::Gamepad(btn.button_type))
+        })
+        .or(if scroll > 0.0 {
+            Some(Binding::WheelUp)
+        } else if scroll < 0.0 {
+            Some(Binding::WheelDown)
+        } else {
+            None
+        });
+
+    if let Some(binding) = binding {
+        bindings.rebind(action, binding);
+        rebinding.0 = None;
+    }
+}
+
+pub fn update_binding_txts(
+    bindings: Res<Bindings>,
+    rebinding: Res<Rebinding>,
+    mut txt_q: Query<(&mut Text, &BindingTxt)>,
+) {
+    if !bindings.is_changed() && !rebinding.is_changed() {
+        return;
+    }
+
+    for (mut txt, binding_txt) in txt_q.iter_mut() {
+        txt.sections[0].value = if rebinding.0 == Some(binding_txt.0) {
+            REBIND_PROMPT.to_string()
+        } else {
+            bindings.describe(binding_txt.0)
+        };
+    }
+}
@@ -0,0 +1,43 @@
+use bevy::prelude::*;
+
+use crate::SettingsState;
+
+pub mod controls_cmps;
+pub mod controls_evs;
+pub mod controls_res;
+mod controls_sys;
+
+use controls_evs::*;
+use controls_res::*;
+use controls_sys::*;
+
+/// shown in place of the bindings of the action being rebound
+pub const REBIND_PROMPT: &str = "Press a key or button (Esc cancels)";
+
+pub struct ControlsPlugin;
+
+impl Plugin for ControlsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ControlsSelection>()
+            .init_resource::<Rebinding>()
+            .add_event::<ControlsItemPressed>()
+            .add_systems(OnEnter(SettingsState::Controls), spawn_controls_menu)
+            .add_systems(
+                OnExit(SettingsState::Controls),
+                (despawn_controls_menu, save_bindings),
+            )
+            .add_systems(
+                Update,
+                (
+                    navigate_controls_actions.run_if(rebinding_inactive),
+                    navigate_controls_mouse.run_if(rebinding_inactive),
+                    highlight_controls_rows,
+                    press_controls_item,
+                    capture_binding.run_if(rebinding_active),
+                    update_binding_txts,
+                )
+                    .chain()
+                    .run_if(in_state(SettingsState::Controls)),
+            );
+    }
+}
@@ -0,0 +1,64 @@
+use bevy::prelude::*;
+
+use crate::SettingsState;
+
+pub mod controls;
+pub mod settings_cmps;
+pub mod settings_evs;
+pub mod settings_res;
+mod settings_sys;
+
+use controls::ControlsPlugin;
+use settings_evs::*;
+use settings_res::*;
+use settings_sys::*;
+
+pub const SETTINGS_FILE: &str = "settings.ron";
+/// resolutions offered in windowed and fullscreen mode
+pub const RESOLUTIONS: [(u32, u32); 5] = [
+    (1280, 720),
+    (1600, 900),
+    (1920, 1080),
+    (2560, 1440),
+    (3840, 2160),
+];
+pub const VOLUME_STEP: f32 = 0.1;
+pub const SENSITIVITY_STEP: f32 = 0.1;
+pub const MIN_SENSITIVITY: f32 = 0.1;
+pub const MAX_SENSITIVITY: f32 = 3.0;
+pub const DEADZONE_STEP: f32 = 0.05;
+pub const MAX_DEADZONE: f32 = 0.9;
+pub const SETTINGS_BTN_COLOR: Color = Color::rgba(0.38, 0.0, 0.99, 0.9);
+pub const SETTINGS_BTN_COLOR_SELECTED: Color = Color::rgb(0.5, 0.0, 1.0);
+pub const SETTINGS_BACKGROUND_COLOR: Color = Color::rgba(0.0, 0.0, 0.0, 0.85);
+
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(ControlsPlugin)
+            .init_resource::<SettingsSelection>()
+            .add_event::<AdjustSetting>()
+            .add_systems(OnEnter(SettingsState::Open), spawn_settings_menu)
+            .add_systems(
+                OnExit(SettingsState::Open),
+                (despawn_settings_menu, save_settings),
+            )
+            .add_systems(
+                Update,
+                apply_window_settings.run_if(resource_changed::<Settings>()),
+            )
+            .add_systems(
+                Update,
+                (
+                    navigate_settings_actions,
+                    navigate_settings_mouse,
+                    highlight_settings_rows,
+                    adjust_setting,
+                    update_settings_values,
+                )
+                    .chain()
+                    .run_if(in_state(SettingsState::Open)),
+            );
+    }
+}
@@ -0,0 +1,66 @@
+use bevy::prelude::*;
+
+#[derive(Component)]
+pub struct SettingsMenu;
+
+#[derive(Component, Clone, Copy, PartialEq)]
+pub enum SettingsItem {
+    MasterVolume,
+    MusicVolume,
+    SfxVolume,
+    WindowMode,
+    Resolution,
+    Vsync,
+    MouseSensitivity,
+    GamepadSensitivity,
+    Deadzone,
+    InvertY,
+    Controls,
+    Back,
+}
+
+impl SettingsItem {
+    /// rows in the order they are listed in the menu
+    pub const ALL: [SettingsItem; 12] = [
+        SettingsItem::MasterVolume,
+        SettingsItem::MusicVolume,
+        SettingsItem::SfxVolume,
+        SettingsItem::WindowMode,
+        SettingsItem::Resolution,
+        SettingsItem::Vsync,
+        SettingsItem::MouseSensitivity,
+        SettingsItem::GamepadSensitivity,
+        SettingsItem::Deadzone,
+        SettingsItem::InvertY,
+        SettingsItem::Controls,
+        SettingsItem::Back,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SettingsItem::MasterVolume => "Master Volume",
+            SettingsItem::MusicVolume => "Music Volume",
+            SettingsItem::SfxVolume => "SFX Volume",
+            SettingsItem::WindowMode => "Window Mode",
+            SettingsItem::Resolution => "Resolution",
+            SettingsItem::Vsync => "VSync",
+            SettingsItem::MouseSensitivity => "Mouse Sensitivity",
+            SettingsItem::GamepadSensitivity => "Gamepad Sensitivity",
+            SettingsItem::Deadzone => "Deadzone",
+            SettingsItem::InvertY => "Invert Y",
+            SettingsItem::Controls => "Controls",
+            SettingsItem::Back => "Back",
+        }
+    }
+}
+
+/// Arrow button stepping a setting down (-1) or up (1)
+#[derive(Component)]
+pub struct SettingsArrow {
+    pub item: SettingsItem,
+    pub step: i32,
+}
+
+/// Text showing the current value of a setting
+#[derive(Component)]
+pub struct SettingsValueTxt(pub SettingsItem);
@@ -0,0 +1,10 @@
+use bevy::prelude::Event;
+
+use super::settings_cmps::SettingsItem;
+
+/// A setting was stepped with any input device, confirming a row steps it up
+#[derive(Event)]
+pub struct AdjustSetting {
+    pub item: SettingsItem,
+    pub step: i32,
+}
@@ -0,0 +1,207 @@
+use std::path::PathBuf;
+
+use bevy::{
+    prelude::*,
+    window::{PresentMode, WindowMode, WindowResolution},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::storage::{load_ron, project_dirs, save_ron};
+
+use super::{
+    settings_cmps::SettingsItem, DEADZONE_STEP, MAX_DEADZONE, MAX_SENSITIVITY, MIN_SENSITIVITY,
+    RESOLUTIONS, SENSITIVITY_STEP, SETTINGS_FILE, VOLUME_STEP,
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum DisplayMode {
+    Windowed,
+    Borderless,
+    Fullscreen,
+}
+
+impl DisplayMode {
+    pub const ALL: [DisplayMode; 3] = [
+        DisplayMode::Windowed,
+        DisplayMode::Borderless,
+        DisplayMode::Fullscreen,
+    ];
+
+    pub fn window_mode(&self) -> WindowMode {
+        match self {
+            DisplayMode::Windowed => WindowMode::Windowed,
+            DisplayMode::Borderless => WindowMode::BorderlessFullscreen,
+            DisplayMode::Fullscreen => WindowMode::Fullscreen,
+        }
+    }
+}
+
+/// Player options, saved to the user config directory and applied live
+#[derive(Resource, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct Settings {
+    pub master_volume: f32,
+    pub music_volume: f32,
+    pub sfx_volume: f32,
+    pub window_mode: DisplayMode,
+    pub resolution: (u32, u32),
+    pub vsync: bool,
+    pub mouse_sensitivity: f32,
+    /// multiplier on the default gamepad camera sensitivity
+    pub gamepad_sensitivity: f32,
+    pub deadzone: f32,
+    pub invert_y: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            master_volume: 1.0,
+            music_volume: 1.0,
+            sfx_volume: 1.0,
+            window_mode: DisplayMode::Borderless,
+            resolution: (1920, 1080),
+            vsync: true,
+            mouse_sensitivity: 1.0,
+            gamepad_sensitivity: 1.0,
+            deadzone: 0.5,
+            invert_y: false,
+        }
+    }
+}
+
+impl Settings {
+    /// Read the settings from the config file, falling back to the defaults
+    pub fn load() -> Self {
+        save_path()
+            .and_then(|path| load_ron(&path))
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        if let Some(path) = save_path() {
+            save_ron(&path, self);
+        }
+    }
+
+    pub fn music_volume(&self) -> f32 {
+        self.master_volume * self.music_volume
+    }
+
+    pub fn sfx_volume(&self) -> f32 {
+        self.master_volume * self.sfx_volume
+    }
+
+    pub fn window_resolution(&self) -> WindowResolution {
+        WindowResolution::new(self.resolution.0 as f32, self.resolution.1 as f32)
+    }
+
+    pub fn present_mode(&self) -> PresentMode {
+        if self.vsync {
+            PresentMode::AutoVsync
+        } else {
+            PresentMode::AutoNoVsync
+        }
+    }
+
+    /// Step a setting down or up, wrapping choices and clamping values
+    pub fn adjust(&mut self, item: SettingsItem, step: i32) {
+        match item {
+            SettingsItem::MasterVolume => {
+                self.master_volume = step_value(self.master_volume, step, VOLUME_STEP, 0.0, 1.0)
+            }
+            SettingsItem::MusicVolume => {
+                self.music_volume = step_value(self.music_volume, step, VOLUME_STEP, 0.0, 1.0)
+            }
+            SettingsItem::SfxVolume => {
+                self.sfx_volume = step_value(self.sfx_volume, step, VOLUME_STEP, 0.0, 1.0)
+            }
+            SettingsItem::WindowMode => {
+                let idx = DisplayMode::ALL
+                    .iter()
+                    .position(|mode| *mode == self.window_mode)
+                    .unwrap_or(0);
+                self.window_mode = DisplayMode::ALL[wrap(idx, step, DisplayMode::ALL.len())];
+            }
+            SettingsItem::Resolution => {
+                let idx = RESOLUTIONS
+                    .iter()
+                    .position(|res| *res == self.resolution)
+                    .unwrap_or(0);
+                self.resolution = RESOLUTIONS[wrap(idx, step, RESOLUTIONS.len())];
+            }
+            SettingsItem::Vsync => self.vsync = !self.vsync,
+            SettingsItem::MouseSensitivity => {
+                self.mouse_sensitivity = step_value(
+                    self.mouse_sensitivity,
+                    step,
+                    SENSITIVITY_STEP,
+                    MIN_SENSITIVITY,
+                    MAX_SENSITIVITY,
+                )
+            }
+            SettingsItem::GamepadSensitivity => {
+                self.gamepad_sensitivity = step_value(
+                    self.gamepad_sensitivity,
+                    step,
+                    SENSITIVITY_STEP,
+                    MIN_SENSITIVITY,
+                    MAX_SENSITIVITY,
+                )
+            }
+            SettingsItem::Deadzone => {
+                self.deadzone = step_value(self.deadzone, step, DEADZONE_STEP, 0.0, MAX_DEADZONE)
+            }
+            SettingsItem::InvertY => self.invert_y = !self.invert_y,
+            SettingsItem::Controls | SettingsItem::Back => (),
+        }
+    }
+
+    /// Current value of a setting as shown in the menu
+    pub fn display(&self, item: SettingsItem) -> String {
+        let on_off = |on: bool| if on { "On" } else { "Off" }.to_string();
+
+        match item {
+            SettingsItem::MasterVolume => format!("{:.0}%", self.master_volume * 100.0),
+            SettingsItem::MusicVolume => format!("{:.0}%", self.music_volume * 100.0),
+            SettingsItem::SfxVolume => format!("{:.0}%", self.sfx_volume * 100.0),
+            SettingsItem::WindowMode => format!("{:?}", self.window_mode),
+            SettingsItem::Resolution => format!("{}x{}", self.resolution.0, self.resolution.1),
+            SettingsItem::Vsync => on_off(self.vsync),
+            SettingsItem::MouseSensitivity => format!("{:.1}x", self.mouse_sensitivity),
+            SettingsItem::GamepadSensitivity => format!("{:.1}x", self.gamepad_sensitivity),
+            SettingsItem::Deadzone => format!("{:.2}", self.deadzone),
+            SettingsItem::InvertY => on_off(self.invert_y),
+            SettingsItem::Controls | SettingsItem::Back => String::new(),
+        }
+    }
+}
+
+/// Row highlighted in the settings menu
+#[derive(Resource, Default)]
+pub struct SettingsSelection(pub usize);
+
+impl SettingsSelection {
+    pub fn item(&self) -> SettingsItem {
+        SettingsItem::ALL[self.0]
+    }
+
+    /// move the selection up or down, wrapping around at either end
+    pub fn step(&mut self, step: i32) {
+        self.0 = wrap(self.0, step, SettingsItem::ALL.len());
+    }
+}
+
+fn wrap(idx: usize, step: i32, len: usize) -> usize {
+    (idx as i32 + step).rem_euclid(len as i32) as usize
+}
+
+/// rounded so repeated steps don't drift away from the displayed value
+fn step_value(value: f32, step: i32, size: f32, min: f32, max: f32) -> f32 {
+    let value = (value + step as f32 * size).clamp(min, max);
+    (value * 100.0).round() / 100.0
+}
+
+fn save_path() -> Option<PathBuf> {
+    project_dirs().map(|dirs| dirs.config_dir().join(SETTINGS_FILE))
+}
@@ -0,0 +1,271 @@
+use bevy::{prelude::*, window::PrimaryWindow};
+
+use crate::{
+    actions::actions_res::{Action, InputActions},
+    debug::debug_res::EnableDebugMode,
+    SettingsState,
+};
+
+use super::{
+    settings_cmps::*, settings_evs::AdjustSetting, settings_res::*, SETTINGS_BACKGROUND_COLOR,
+    SETTINGS_BTN_COLOR, SETTINGS_BTN_COLOR_SELECTED,
+};
+
+pub fn spawn_settings_menu(
+    mut cmds: Commands,
+    assets: Res<AssetServer>,
+    settings: Res<Settings>,
+    mut selection: ResMut<SettingsSelection>,
+) {
+    *selection = SettingsSelection::default();
+    let font = assets.load("fonts/PermanentMarker-Regular.ttf");
+
+    // drawn on top of the main menu or the pause menu
+    let container = (
+        NodeBundle {
+            background_color: SETTINGS_BACKGROUND_COLOR.into(),
+            style: Style {
+                align_items: AlignItems::Center,
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                ..default()
+            },
+            z_index: ZIndex::Global(10),
+            ..default()
+        },
+        SettingsMenu,
+        Name::new("Settings Menu"),
+    );
+
+    let title_txt = (
+        TextBundle::from_section(
+            "Settings",
+            TextStyle {
+                color: Color::WHITE,
+                font: font.clone(),
+                font_size: 60.0,
+            },
+        ),
+        Name::new("Settings Text"),
+    );
+
+    let txt_style = TextStyle {
+        color: Color::WHITE,
+        font,
+        font_size: 30.0,
+    };
+
+    cmds.spawn(container).with_children(|parent| {
+        parent.spawn(title_txt);
+
+        for (idx, item) in SettingsItem::ALL.into_iter().enumerate() {
+            let color = if idx == selection.0 {
+                SETTINGS_BTN_COLOR_SELECTED
+            } else {
+                SETTINGS_BTN_COLOR
+            };
+
+            let row = (
+                ButtonBundle {
+                    background_color: color.into(),
+                    style: Style {
+                        align_items: AlignItems::Center,
+                        justify_content: JustifyContent::SpaceBetween,
+                        width: Val::Px(600.0),
+                        height: Val::Px(45.0),
+                        margin: UiRect::top(Val::Px(8.0)),
+                        padding: UiRect::horizontal(Val::Px(15.0)),
+                        ..default()
+                    },
+                    ..default()
+                },
+                item,
+                Name::new(format!("{} Row", item.label())),
+            );
+
+            parent.spawn(row).with_children(|parent| {
+                parent.spawn(TextBundle::from_section(item.label(), txt_style.clone()));
+
+                // rows that open something have no value to step
+                if matches!(item, SettingsItem::Controls | SettingsItem::Back) {
+                    return;
+                }
+
+                // < value >
+                parent
+                    .spawn(NodeBundle {
+                        style: Style {
+                            align_items: AlignItems::Center,
+                            column_gap: Val::Px(15.0),
+                            ..default()
+                        },
+                        ..default()
+                    })
+                    .with_children(|parent| {
+                        for (step, arrow) in [(-1, "<"), (1, ">")] {
+                            let arrow_btn = (
+                                ButtonBundle {
+                                    background_color: Color::NONE.into(),
+                                    style: Style {
+                                        justify_content: JustifyContent::Center,
+                                        width: Val::Px(30.0),
+                                        ..default()
+                                    },
+                                    ..default()
+                                },
+                                SettingsArrow { item, step },
+                            );
+
+                            // value sits between the two arrows
+                            if step == 1 {
+                                parent.spawn((
+                                    TextBundle::from_section(
+                                        settings.display(item),
+                                        txt_style.clone(),
+                                    ),
+                                    SettingsValueTxt(item),
+                                ));
+                            }
+
+                            parent.spawn(arrow_btn).with_children(|parent| {
+                                parent.spawn(TextBundle::from_section(arrow, txt_style.clone()));
+                            });
+                        }
+                    });
+            });
+        }
+    });
+}
+
+pub fn despawn_settings_menu(mut cmds: Commands, menu_q: Query<Entity, With<SettingsMenu>>) {
+    for ent in menu_q.iter() {
+        cmds.entity(ent).despawn_recursive();
+    }
+}
+
+pub fn save_settings(settings: Res<Settings>) {
+    settings.save();
+}
+
+/// Window mode, resolution and vsync, debug mode always stays windowed
+pub fn apply_window_settings(
+    settings: Res<Settings>,
+    debug: Res<EnableDebugMode>,
+    mut window_q: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    let Ok(mut window) = window_q.get_single_mut() else { return };
+
+    if !debug.0 {
+        window.mode = settings.window_mode.window_mode();
+    }
+
+    let (width, height) = settings.resolution;
+    window.resolution.set(width as f32, height as f32);
+    window.present_mode = settings.present_mode();
+}
+
+/// Menu up & down move the selection, left & right step the setting, Back closes the menu
+pub fn navigate_settings_actions(
+    actions: Res<InputActions>,
+    mut selection: ResMut<SettingsSelection>,
+    mut adjust_evw: EventWriter<AdjustSetting>,
+) {
+    if actions.just_pressed(Action::MenuUp) {
+        selection.step(-1);
+    }
+
+    if actions.just_pressed(Action::MenuDown) {
+        selection.step(1);
+    }
+
+    let item = selection.item();
+
+    if actions.just_pressed(Action::MenuLeft) {
+        adjust_evw.send(AdjustSetting { item, step: -1 });
+    }
+
+    if actions.just_pressed(Action::MenuRight) || actions.just_pressed(Action::Confirm) {
+        adjust_evw.send(AdjustSetting { item, step: 1 });
+    }
+
+    if actions.just_pressed(Action::Back) {
+        adjust_evw.send(AdjustSetting {
+            item: SettingsItem::Back,
+            step: 1,
+        });
+    }
+}
+
+/// Hovering a row selects it, clicking a row or one of its arrows steps it
+pub fn navigate_settings_mouse(
+    row_q: Query<(&Interaction, &SettingsItem), Changed<Interaction>>,
+    arrow_q: Query<(&Interaction, &SettingsArrow), Changed<Interaction>>,
+    mut selection: ResMut<SettingsSelection>,
+    mut adjust_evw: EventWriter<AdjustSetting>,
+) {
+    let rows = row_q
+        .iter()
+        .map(|(interaction, item)| (interaction, *item, 1));
+    let arrows = arrow_q
+        .iter()
+        .map(|(interaction, arrow)| (interaction, arrow.item, arrow.step));
+
+    for (interaction, item, step) in rows.chain(arrows) {
+        let Some(idx) = SettingsItem::ALL.iter().position(|i| *i == item) else {
+            continue;
+        };
+
+        match *interaction {
+            Interaction::Pressed => adjust_evw.send(AdjustSetting { item, step }),
+            Interaction::Hovered => selection.0 = idx,
+            Interaction::None => (),
+        }
+    }
+}
+
+pub fn highlight_settings_rows(
+    selection: Res<SettingsSelection>,
+    mut row_q: Query<(&SettingsItem, &mut BackgroundColor)>,
+) {
+    if !selection.is_changed() {
+        return;
+    }
+
+    for (item, mut background_clr) in row_q.iter_mut() {
+        *background_clr = if *item == selection.item() {
+            SETTINGS_BTN_COLOR_SELECTED.into()
+        } else {
+            SETTINGS_BTN_COLOR.into()
+        };
+    }
+}
+
+pub fn adjust_setting(
+    mut adjust_evr: EventReader<AdjustSetting>,
+    mut settings: ResMut<Settings>,
+    mut next_settings_state: ResMut<NextState<SettingsState>>,
+) {
+    for ev in adjust_evr.iter() {
+        match ev.item {
+            SettingsItem::Controls => next_settings_state.set(SettingsState::Controls),
+            SettingsItem::Back => next_settings_state.set(SettingsState::Closed),
+            item => settings.adjust(item, ev.step),
+        }
+    }
+}
+
+pub fn update_settings_values(
+    settings: Res<Settings>,
+    mut txt_q: Query<(&mut Text, &SettingsValueTxt)>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+
+    for (mut txt, value) in txt_q.iter_mut() {
+        txt.sections[0].value = settings.display(value.0);
+    }
+}
@@ -0,0 +1,66 @@
+use std::{
+    fs::{self, File},
+    io::{Read, Write},
+    path::Path,
+};
+
+use bevy::prelude::*;
+use directories::ProjectDirs;
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use ron::ser::PrettyConfig;
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Where settings, bindings and high scores are saved
+pub fn project_dirs() -> Option<ProjectDirs> {
+    ProjectDirs::from("com", "TheDevBlog", "Zombeats")
+}
+
+/// Read a saved RON file, None if it doesn't exist yet or can't be parsed
+pub fn load_ron<T: DeserializeOwned>(path: &Path) -> Option<T> {
+    let contents = fs::read_to_string(path).ok()?;
+
+    ron::from_str(&contents)
+        .map_err(|e| warn!("Could not read {}: {}", path.display(), e))
+        .ok()
+}
+
+pub fn save_ron<T: Serialize>(path: &Path, value: &T) {
+    let result = ron::ser::to_string_pretty(value, PrettyConfig::default())
+        .map_err(|e| e.to_string())
+        .and_then(|contents| {
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+            }
+            fs::write(path, contents).map_err(|e| e.to_string())
+        });
+
+    if let Err(e) = result {
+        warn!("Could not save {}: {}", path.display(), e);
+    }
+}
+
+/// Read a gzip compressed RON file, for files too big to keep as plain text
+pub fn load_ron_gz<T: DeserializeOwned>(path: &Path) -> Result<T, String> {
+    let file = File::open(path).map_err(|e| e.to_string())?;
+    let mut contents = String::new();
+    GzDecoder::new(file)
+        .read_to_string(&mut contents)
+        .map_err(|e| e.to_string())?;
+
+    ron::from_str(&contents).map_err(|e| e.to_string())
+}
+
+pub fn save_ron_gz<T: Serialize>(path: &Path, value: &T) -> Result<(), String> {
+    let contents = ron::to_string(value).map_err(|e| e.to_string())?;
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+
+    let file = File::create(path).map_err(|e| e.to_string())?;
+    let mut encoder = GzEncoder::new(file, Compression::default());
+    encoder
+        .write_all(contents.as_bytes())
+        .and_then(|_| encoder.finish().map(|_| ()))
+        .map_err(|e| e.to_string())
+}